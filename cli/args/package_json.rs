@@ -67,6 +67,7 @@ pub fn get_local_package_json_version_reqs(
     if value.starts_with("workspace:")
       || value.starts_with("file:")
       || value.starts_with("git:")
+      || value.starts_with("github:")
       || value.starts_with("http:")
       || value.starts_with("https:")
     {
@@ -235,6 +236,7 @@ mod test {
       ("work-test".to_string(), "workspace:1.1.1".to_string()),
       ("file-test".to_string(), "file:something".to_string()),
       ("git-test".to_string(), "git:something".to_string()),
+      ("github-test".to_string(), "github:user/repo".to_string()),
       ("http-test".to_string(), "http://something".to_string()),
       ("https-test".to_string(), "https://something".to_string()),
     ]));
@@ -250,6 +252,10 @@ mod test {
           "git-test".to_string(),
           Err("Not implemented scheme 'git'".to_string()),
         ),
+        (
+          "github-test".to_string(),
+          Err("Not implemented scheme 'github'".to_string()),
+        ),
         (
           "http-test".to_string(),
           Err("Not implemented scheme 'http'".to_string()),
@@ -157,6 +157,19 @@ const EXEC_TIME_BENCHMARKS: &[(&str, &[&str], Option<i32>)] = &[
     ],
     None,
   ),
+  (
+    "publish_unfurl",
+    &[
+      "publish",
+      "--dry-run",
+      "--no-check",
+      "--token",
+      "dummy",
+      "--config",
+      "tests/testdata/benches/publish_unfurl/deno.json",
+    ],
+    None,
+  ),
 ];
 
 const RESULT_KEYS: &[&str] =
@@ -9,6 +9,7 @@ use deno_core::futures::future;
 use deno_core::futures::future::LocalBoxFuture;
 use deno_core::futures::FutureExt;
 use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
 use deno_core::ModuleCodeString;
 use deno_core::ModuleSpecifier;
 use deno_graph::source::NpmPackageReqResolution;
@@ -37,6 +38,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::args::package_json::PackageJsonDepValueParseError;
 use crate::args::package_json::PackageJsonDeps;
 use crate::args::JsxImportSourceConfig;
 use crate::args::PackageJsonDepsProvider;
@@ -353,6 +355,22 @@ impl MappedSpecifierResolver {
       Ok(MappedResolution::None)
     }
   }
+
+  pub fn maybe_import_map(&self) -> Option<&Arc<ImportMap>> {
+    self.maybe_import_map.as_ref()
+  }
+
+  /// Gets the error for the package.json dependency the specifier refers
+  /// to when its version requirement could not be parsed, such as for
+  /// git or file dependencies.
+  pub fn package_json_dep_error(
+    &self,
+    specifier: &str,
+  ) -> Option<&PackageJsonDepValueParseError> {
+    let deps = self.package_json_deps_provider.deps()?;
+    let (req_result, _) = find_package_json_dep(specifier, deps)?;
+    req_result.as_ref().err()
+  }
 }
 
 /// A resolver that takes care of resolution, taking into account loaded
@@ -703,22 +721,36 @@ fn resolve_package_json_dep(
   specifier: &str,
   deps: &PackageJsonDeps,
 ) -> Result<Option<ModuleSpecifier>, AnyError> {
+  let Some((req_result, path)) = find_package_json_dep(specifier, deps) else {
+    return Ok(None);
+  };
+  let req = req_result.as_ref().map_err(|err| {
+    anyhow!(
+      "Parsing version constraints in the application-level package.json is more strict at the moment.\n\n{:#}",
+      err.clone()
+    )
+  })?;
+  Ok(Some(ModuleSpecifier::parse(&format!("npm:{req}{path}"))?))
+}
+
+/// Finds the package.json dependency a bare specifier refers to, along with
+/// the sub path of the specifier within that package.
+fn find_package_json_dep<'a, 'b>(
+  specifier: &'b str,
+  deps: &'a PackageJsonDeps,
+) -> Option<(
+  &'a Result<PackageReq, PackageJsonDepValueParseError>,
+  &'b str,
+)> {
   for (bare_specifier, req_result) in deps {
     if specifier.starts_with(bare_specifier) {
       let path = &specifier[bare_specifier.len()..];
       if path.is_empty() || path.starts_with('/') {
-        let req = req_result.as_ref().map_err(|err| {
-          anyhow!(
-            "Parsing version constraints in the application-level package.json is more strict at the moment.\n\n{:#}",
-            err.clone()
-          )
-        })?;
-        return Ok(Some(ModuleSpecifier::parse(&format!("npm:{req}{path}"))?));
+        return Some((req_result, path));
       }
     }
   }
-
-  Ok(None)
+  None
 }
 
 impl NpmResolver for CliGraphResolver {
@@ -799,6 +831,7 @@ impl NpmResolver for CliGraphResolver {
 struct SloppyImportsStatCache {
   fs: Arc<dyn FileSystem>,
   cache: Mutex<HashMap<PathBuf, Option<SloppyImportsFsEntry>>>,
+  dir_entry_names: Mutex<HashMap<PathBuf, Option<Vec<String>>>>,
 }
 
 impl SloppyImportsStatCache {
@@ -806,6 +839,7 @@ impl SloppyImportsStatCache {
     Self {
       fs,
       cache: Default::default(),
+      dir_entry_names: Default::default(),
     }
   }
 
@@ -825,6 +859,30 @@ impl SloppyImportsStatCache {
     cache.insert(path.to_owned(), entry);
     entry
   }
+
+  /// Gets the name an entry of the directory has on disk, preferring an
+  /// exact match over a case insensitive one.
+  pub fn dir_entry_name(&self, dir_path: &Path, name: &str) -> Option<String> {
+    let mut dir_entry_names = self.dir_entry_names.lock();
+    if !dir_entry_names.contains_key(dir_path) {
+      let names = self
+        .fs
+        .read_dir_sync(dir_path)
+        .ok()
+        .map(|entries| entries.into_iter().map(|entry| entry.name).collect());
+      dir_entry_names.insert(dir_path.to_owned(), names);
+    }
+    let names = dir_entry_names.get(dir_path)?.as_ref()?;
+    names
+      .iter()
+      .find(|entry_name| *entry_name == name)
+      .or_else(|| {
+        names
+          .iter()
+          .find(|entry_name| entry_name.eq_ignore_ascii_case(name))
+      })
+      .cloned()
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1138,6 +1196,114 @@ impl SloppyImportsResolver {
       self.stat_cache.stat_sync(path)
     })
   }
+
+  /// Gets if the specifier points at a file.
+  pub fn is_file(&self, specifier: &ModuleSpecifier) -> bool {
+    specifier_to_file_path(specifier)
+      .map(|path| {
+        self.stat_cache.stat_sync(&path) == Some(SloppyImportsFsEntry::File)
+      })
+      .unwrap_or(false)
+  }
+
+  /// Gets the file specifier with its symlinks resolved. Returns `None`
+  /// when the path doesn't exist.
+  pub fn canonicalize(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<ModuleSpecifier> {
+    let path = specifier_to_file_path(specifier).ok()?;
+    let real_path = self.stat_cache.fs.realpath_sync(&path).ok()?;
+    if specifier.path().ends_with('/') {
+      ModuleSpecifier::from_directory_path(real_path).ok()
+    } else {
+      ModuleSpecifier::from_file_path(real_path).ok()
+    }
+  }
+
+  /// Resolves an extensionless specifier to the first file that exists with
+  /// one of the extensions of the provided media types, in order.
+  pub fn resolve_with_extension_preference(
+    &self,
+    specifier: &ModuleSpecifier,
+    preference: &[MediaType],
+  ) -> Option<ModuleSpecifier> {
+    if MediaType::from_specifier(specifier) != MediaType::Unknown
+      || self.is_file(specifier)
+    {
+      return None;
+    }
+    let path = specifier_to_file_path(specifier).ok()?;
+    let path = path.to_string_lossy();
+    preference
+      .iter()
+      .map(|media_type| {
+        PathBuf::from(format!("{}{}", path, media_type.as_ts_extension()))
+      })
+      .find(|path| {
+        self.stat_cache.stat_sync(path) == Some(SloppyImportsFsEntry::File)
+      })
+      .and_then(|path| ModuleSpecifier::from_file_path(path).ok())
+  }
+
+  /// Resolves a directory to the module that importing it refers to, which
+  /// is the `main` of its package.json, otherwise its `mod` or `index` file.
+  pub fn resolve_directory_entrypoint(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<ModuleSpecifier> {
+    let path = specifier_to_file_path(specifier).ok()?;
+    if self.stat_cache.stat_sync(&path) != Some(SloppyImportsFsEntry::Dir) {
+      return None;
+    }
+    let package_json_main = self
+      .stat_cache
+      .fs
+      .read_text_file_sync(&path.join("package.json"))
+      .ok()
+      .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+      .and_then(|value| {
+        value.get("main")?.as_str().map(|main| path.join(main))
+      });
+    package_json_main
+      .into_iter()
+      .chain(
+        ["mod.ts", "mod.js", "index.ts", "index.js", "index.mjs"]
+          .iter()
+          .map(|name| path.join(name)),
+      )
+      .find(|path| {
+        self.stat_cache.stat_sync(path) == Some(SloppyImportsFsEntry::File)
+      })
+      .and_then(|path| ModuleSpecifier::from_file_path(path).ok())
+  }
+
+  /// Gets the file specifier with the casing its path has on disk, which
+  /// may differ from the provided specifier on case insensitive file
+  /// systems. Returns `None` when the file doesn't exist.
+  pub fn resolve_on_disk_casing(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<ModuleSpecifier> {
+    let path = specifier_to_file_path(specifier).ok()?;
+    let mut cased_path = PathBuf::new();
+    for component in path.components() {
+      let std::path::Component::Normal(name) = component else {
+        cased_path.push(component);
+        continue;
+      };
+      let name = name.to_str()?;
+      let entry_name = self.stat_cache.dir_entry_name(&cased_path, name)?;
+      cased_path.push(entry_name);
+    }
+    let mut cased = ModuleSpecifier::from_file_path(cased_path).ok()?;
+    // keep the trailing slash of a directory
+    if specifier.path().ends_with('/') && !cased.path().ends_with('/') {
+      let path = format!("{}/", cased.path());
+      cased.set_path(&path);
+    }
+    Some(cased)
+  }
 }
 
 #[cfg(test)]
@@ -129,6 +129,13 @@ impl PublishDiagnostic {
 
     (self.code().to_string(), specifier, source_pos)
   }
+
+  fn highlight_style(&self) -> DiagnosticSnippetHighlightStyle {
+    match self.level() {
+      DiagnosticLevel::Error => DiagnosticSnippetHighlightStyle::Error,
+      DiagnosticLevel::Warning => DiagnosticSnippetHighlightStyle::Warning,
+    }
+  }
 }
 
 impl Diagnostic for PublishDiagnostic {
@@ -139,6 +146,13 @@ impl Diagnostic for PublishDiagnostic {
         ..
       }) => DiagnosticLevel::Warning,
       FastCheck(_) => DiagnosticLevel::Error,
+      SpecifierUnfurl(SpecifierUnfurlerDiagnostic::SloppyImportRewrite {
+        ..
+      })
+      | SpecifierUnfurl(SpecifierUnfurlerDiagnostic::MissingFile { .. })
+      | SpecifierUnfurl(SpecifierUnfurlerDiagnostic::ParseError { .. }) => {
+        DiagnosticLevel::Error
+      }
       SpecifierUnfurl(_) => DiagnosticLevel::Warning,
       InvalidPath { .. } => DiagnosticLevel::Error,
       DuplicatePath { .. } => DiagnosticLevel::Error,
@@ -165,7 +179,7 @@ impl Diagnostic for PublishDiagnostic {
     use PublishDiagnostic::*;
     match &self {
       FastCheck(diagnostic) => diagnostic.message(),
-      SpecifierUnfurl(diagnostic) => Cow::Borrowed(diagnostic.message()),
+      SpecifierUnfurl(diagnostic) => diagnostic.detailed_message(),
       InvalidPath { message, .. } => Cow::Borrowed(message.as_str()),
       DuplicatePath { .. } => {
         Cow::Borrowed("package path is a case insensitive duplicate of another path in the package")
@@ -187,11 +201,135 @@ impl Diagnostic for PublishDiagnostic {
           specifier,
           text_info,
           range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::BareNodeBuiltin {
+          specifier,
+          text_info,
+          range,
+        }
+        | SpecifierUnfurlerDiagnostic::DisallowedScheme {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::NonRegistryDependency {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::SelfImport {
+          specifier,
+          text_info,
+          range,
+        }
+        | SpecifierUnfurlerDiagnostic::TypeScriptImport {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::CaseMismatch {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::DeepRelativeImport {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::ParseError {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::ImportMapAliasCycle {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::EmptySpecifier {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::SloppyImportRewrite {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::BackslashInSpecifier {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::CrossExportInternalImport {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::MissingFile {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::NullImportMapEntry {
+          specifier,
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::TestFileImport {
+          specifier,
+          text_info,
+          range,
+          ..
         } => DiagnosticLocation::ModulePosition {
           specifier: Cow::Borrowed(specifier),
           text_info: Cow::Borrowed(text_info),
           source_pos: DiagnosticSourcePos::SourcePos(range.start),
         },
+        SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource {
+          specifier,
+          ..
+        } => DiagnosticLocation::Module {
+          specifier: Cow::Borrowed(specifier),
+        },
+        SpecifierUnfurlerDiagnostic::ImportMapDiagnostic {
+          specifier, ..
+        } => DiagnosticLocation::Module {
+          specifier: Cow::Borrowed(specifier),
+        },
       },
       InvalidPath { path, .. } => {
         DiagnosticLocation::Path { path: path.clone() }
@@ -231,7 +369,7 @@ impl Diagnostic for PublishDiagnostic {
         } => Some(DiagnosticSnippet {
           source: Cow::Borrowed(text_info),
           highlight: DiagnosticSnippetHighlight {
-            style: DiagnosticSnippetHighlightStyle::Warning,
+            style: self.highlight_style(),
             range: DiagnosticSourceRange {
               start: DiagnosticSourcePos::SourcePos(range.start),
               end: DiagnosticSourcePos::SourcePos(range.end),
@@ -239,6 +377,107 @@ impl Diagnostic for PublishDiagnostic {
             description: Some("the unanalyzable dynamic import".into()),
           },
         }),
+        SpecifierUnfurlerDiagnostic::BareNodeBuiltin {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::DisallowedScheme {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::NonRegistryDependency {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::SelfImport {
+          text_info, range, ..
+        }
+        | SpecifierUnfurlerDiagnostic::TypeScriptImport {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::CaseMismatch {
+          text_info, range, ..
+        }
+        | SpecifierUnfurlerDiagnostic::DeepRelativeImport {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::ParseError {
+          text_info, range, ..
+        }
+        | SpecifierUnfurlerDiagnostic::ImportMapAliasCycle {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::EmptySpecifier {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::SloppyImportRewrite {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::BackslashInSpecifier {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::CrossExportInternalImport {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::MissingFile {
+          text_info, range, ..
+        }
+        | SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::NullImportMapEntry {
+          text_info,
+          range,
+          ..
+        }
+        | SpecifierUnfurlerDiagnostic::TestFileImport {
+          text_info,
+          range,
+          ..
+        } => Some(DiagnosticSnippet {
+          source: Cow::Borrowed(text_info),
+          highlight: DiagnosticSnippetHighlight {
+            style: self.highlight_style(),
+            range: DiagnosticSourceRange {
+              start: DiagnosticSourcePos::SourcePos(range.start),
+              end: DiagnosticSourcePos::SourcePos(range.end),
+            },
+            description: Some("the specifier".into()),
+          },
+        }),
+        SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource {
+          ..
+        } => None,
+        SpecifierUnfurlerDiagnostic::ImportMapDiagnostic { .. } => None,
       },
       PublishDiagnostic::InvalidPath { .. } => None,
       PublishDiagnostic::DuplicatePath { .. } => None,
@@ -271,7 +510,70 @@ impl Diagnostic for PublishDiagnostic {
   fn hint(&self) -> Option<Cow<'_, str>> {
     match &self {
       PublishDiagnostic::FastCheck(diagnostic) => diagnostic.hint(),
-      PublishDiagnostic::SpecifierUnfurl(_) => None,
+      PublishDiagnostic::SpecifierUnfurl(diagnostic) => match diagnostic {
+        SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::BareNodeBuiltin { .. } => Some(
+          Cow::Borrowed("add the 'node:' prefix to the specifier, or enable the 'bare-node-builtins' unstable feature"),
+        ),
+        SpecifierUnfurlerDiagnostic::DisallowedScheme { .. } => Some(
+          Cow::Borrowed("replace this import with one using an allowed scheme, or vendor the dependency into your package"),
+        ),
+        SpecifierUnfurlerDiagnostic::NonRegistryDependency { .. } => Some(
+          Cow::Borrowed("depend on a version of the package published to npm, or vendor the dependency into your package"),
+        ),
+        SpecifierUnfurlerDiagnostic::SelfImport { .. } => Some(
+          Cow::Borrowed("remove the import"),
+        ),
+        SpecifierUnfurlerDiagnostic::TypeScriptImport { .. } => Some(
+          Cow::Borrowed("import a JavaScript module instead, or provide a build of the package for JavaScript consumers"),
+        ),
+        SpecifierUnfurlerDiagnostic::CaseMismatch { .. } => Some(
+          Cow::Borrowed("change the specifier to match the casing of the file on disk"),
+        ),
+        SpecifierUnfurlerDiagnostic::DeepRelativeImport { .. } => Some(
+          Cow::Borrowed("add an alias for the directory to the import map and import from that instead"),
+        ),
+        SpecifierUnfurlerDiagnostic::ParseError { .. } => Some(
+          Cow::Borrowed("fix the syntax error"),
+        ),
+        SpecifierUnfurlerDiagnostic::ImportMapAliasCycle { .. } => Some(
+          Cow::Borrowed("change one of the import map entries to map to a URL or path"),
+        ),
+        SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource { .. } => None,
+        SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport { .. } => Some(
+          Cow::Borrowed("import the directory's entrypoint module directly, for example './mod.ts'"),
+        ),
+        SpecifierUnfurlerDiagnostic::EmptySpecifier { .. } => Some(
+          Cow::Borrowed("specify the module to import"),
+        ),
+        SpecifierUnfurlerDiagnostic::SloppyImportRewrite { .. } => Some(
+          Cow::Borrowed("specify the file extension or path to the index file explicitly"),
+        ),
+        SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch { .. } => Some(
+          Cow::Borrowed("remove or rename the import map entries that overlap"),
+        ),
+        SpecifierUnfurlerDiagnostic::BackslashInSpecifier { .. } => Some(
+          Cow::Borrowed("use forward slashes in the specifier"),
+        ),
+        SpecifierUnfurlerDiagnostic::CrossExportInternalImport { .. } => Some(
+          Cow::Borrowed("import the other export's entrypoint instead"),
+        ),
+        SpecifierUnfurlerDiagnostic::ImportMapDiagnostic { .. } => Some(
+          Cow::Borrowed("fix the import map entry"),
+        ),
+        SpecifierUnfurlerDiagnostic::MissingFile { .. } => Some(
+          Cow::Borrowed("change the specifier to point at an existing file, or add the missing file to the package"),
+        ),
+        SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch { key, .. } => Some(
+          Cow::Owned(format!("import a module from the directory the import map entry points at, such as '{}mod.ts'", key)),
+        ),
+        SpecifierUnfurlerDiagnostic::NullImportMapEntry { .. } => Some(
+          Cow::Borrowed("give the import map entry a value, or remove the import"),
+        ),
+        SpecifierUnfurlerDiagnostic::TestFileImport { .. } => Some(
+          Cow::Borrowed("move the shared code out of the test module, or remove the import"),
+        ),
+      },
       PublishDiagnostic::InvalidPath { .. } => Some(
         Cow::Borrowed("rename or remove the file, or add it to 'publish.exclude' in the config file"),
       ),
@@ -301,6 +603,90 @@ impl Diagnostic for PublishDiagnostic {
           Cow::Borrowed("dynamic imports that can not be analyzed at publish time will not be rewritten automatically"),
           Cow::Borrowed("make sure the dynamic import is resolvable at runtime without an import map / package.json")
         ]),
+        SpecifierUnfurlerDiagnostic::BareNodeBuiltin { .. } => Cow::Borrowed(&[
+          Cow::Borrowed("bare node built-in specifiers only resolve when the 'bare-node-builtins' unstable feature is enabled"),
+          Cow::Borrowed("the specifier was left unchanged, so it will likely fail to resolve for consumers of this package"),
+        ]),
+        SpecifierUnfurlerDiagnostic::DisallowedScheme { resolved, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import was resolved to '{}'", resolved)),
+          Cow::Owned(format!("the '{}:' scheme is not allowed by the registry", resolved.scheme())),
+        ]),
+        SpecifierUnfurlerDiagnostic::NonRegistryDependency { scheme, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the dependency is declared in the package.json using the '{}:' protocol", scheme)),
+          Cow::Borrowed("consumers of this package can only install dependencies that are published to a registry"),
+        ]),
+        SpecifierUnfurlerDiagnostic::SelfImport { .. } => Cow::Borrowed(&[
+          Cow::Borrowed("the specifier resolves to the module it is imported from, which is usually a mistake"),
+        ]),
+        SpecifierUnfurlerDiagnostic::TypeScriptImport { resolved, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import was resolved to '{}'", resolved)),
+          Cow::Borrowed("plain JavaScript and Node.js consumers can not import TypeScript modules without a build step"),
+        ]),
+        SpecifierUnfurlerDiagnostic::CaseMismatch { on_disk, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the file on disk is '{}'", on_disk)),
+          Cow::Borrowed("the specifier was rewritten to match, since imports with the wrong casing fail on case sensitive file systems"),
+        ]),
+        SpecifierUnfurlerDiagnostic::DeepRelativeImport { depth, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the unfurled specifier goes up {} directories", depth)),
+          Cow::Borrowed("deeply nested relative imports break easily when files are moved"),
+        ]),
+        SpecifierUnfurlerDiagnostic::ParseError { message, .. } => Cow::Owned(vec![
+          Cow::Borrowed(message.as_str()),
+          Cow::Borrowed("the module was left unchanged, so its specifiers were not unfurled"),
+        ]),
+        SpecifierUnfurlerDiagnostic::ImportMapAliasCycle { chain, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import map entries alias each other: {}", chain.join(" -> "))),
+          Cow::Borrowed("the specifier was left unchanged"),
+        ]),
+        SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource { message, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the rewritten source failed to parse: {}", message)),
+          Cow::Borrowed("the module was published without unfurling its specifiers"),
+          Cow::Borrowed("this is a bug in Deno, please report it"),
+        ]),
+        SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport { .. } => Cow::Borrowed(&[
+          Cow::Borrowed("the directory has no package.json 'main', 'mod' file, or 'index' file to import"),
+          Cow::Borrowed("directory imports can not be resolved once the package is published"),
+        ]),
+        SpecifierUnfurlerDiagnostic::EmptySpecifier { .. } => Cow::Borrowed(&[
+          Cow::Borrowed("an empty specifier doesn't refer to a module"),
+          Cow::Borrowed("the specifier was left unchanged"),
+        ]),
+        SpecifierUnfurlerDiagnostic::SloppyImportRewrite { resolved, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the specifier resolves to '{}' using sloppy imports", resolved)),
+          Cow::Borrowed("sloppy imports are configured to be errors for this package"),
+        ]),
+        SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch { keys, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the specifier matches the import map entries {}", keys.iter().map(|key| format!("'{}'", key)).collect::<Vec<_>>().join(", "))),
+          Cow::Borrowed("the most specific entry is used"),
+        ]),
+        SpecifierUnfurlerDiagnostic::BackslashInSpecifier { .. } => Cow::Borrowed(&[
+          Cow::Borrowed("specifiers are URLs, which are separated by forward slashes"),
+          Cow::Borrowed("the specifier was rewritten with forward slashes, but it won't resolve in other tools"),
+        ]),
+        SpecifierUnfurlerDiagnostic::CrossExportInternalImport { export, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the imported module is internal to the '{}' export", export)),
+          Cow::Borrowed("the internals of an export may change without it being a breaking change"),
+        ]),
+        SpecifierUnfurlerDiagnostic::ImportMapDiagnostic { message, .. } => Cow::Owned(vec![
+          Cow::Owned(message.clone()),
+          Cow::Borrowed("specifiers that would resolve with the import map entry may be unfurled incorrectly"),
+        ]),
+        SpecifierUnfurlerDiagnostic::MissingFile { resolved, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import was resolved to '{}', which does not exist", resolved)),
+          Cow::Borrowed("the import would fail for consumers of the published package"),
+        ]),
+        SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch { key, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import map has an entry for '{}', but not for the specifier without the trailing slash", key)),
+          Cow::Borrowed("the specifier was resolved relative to the module instead"),
+        ]),
+        SpecifierUnfurlerDiagnostic::NullImportMapEntry { key, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import map entry for '{}' is null, so the specifier can not be resolved", key)),
+          Cow::Borrowed("the specifier was left unchanged"),
+        ]),
+        SpecifierUnfurlerDiagnostic::TestFileImport { resolved, .. } => Cow::Owned(vec![
+          Cow::Owned(format!("the import was resolved to '{}', which is named like a test module", resolved)),
+          Cow::Borrowed("test modules are usually excluded from the published package or depend on test only code"),
+        ]),
       },
       PublishDiagnostic::InvalidPath { .. } => Cow::Borrowed(&[
         Cow::Borrowed("to portably support all platforms, including windows, the allowed characters in package paths are limited"),
@@ -328,6 +714,31 @@ impl Diagnostic for PublishDiagnostic {
       PublishDiagnostic::FastCheck(diagnostic) => diagnostic.docs_url(),
       PublishDiagnostic::SpecifierUnfurl(diagnostic) => match diagnostic {
         SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::BareNodeBuiltin { .. } => None,
+        SpecifierUnfurlerDiagnostic::DisallowedScheme { .. } => None,
+        SpecifierUnfurlerDiagnostic::NonRegistryDependency { .. } => None,
+        SpecifierUnfurlerDiagnostic::SelfImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::TypeScriptImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::CaseMismatch { .. } => None,
+        SpecifierUnfurlerDiagnostic::DeepRelativeImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::ParseError { .. } => None,
+        SpecifierUnfurlerDiagnostic::ImportMapAliasCycle { .. } => None,
+        SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource {
+          ..
+        } => None,
+        SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::EmptySpecifier { .. } => None,
+        SpecifierUnfurlerDiagnostic::SloppyImportRewrite { .. } => None,
+        SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch { .. } => None,
+        SpecifierUnfurlerDiagnostic::BackslashInSpecifier { .. } => None,
+        SpecifierUnfurlerDiagnostic::CrossExportInternalImport { .. } => None,
+        SpecifierUnfurlerDiagnostic::ImportMapDiagnostic { .. } => None,
+        SpecifierUnfurlerDiagnostic::MissingFile { .. } => None,
+        SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch {
+          ..
+        } => None,
+        SpecifierUnfurlerDiagnostic::NullImportMapEntry { .. } => None,
+        SpecifierUnfurlerDiagnostic::TestFileImport { .. } => None,
       },
       PublishDiagnostic::InvalidPath { .. } => {
         Some(Cow::Borrowed("https://jsr.io/go/invalid-path"))
@@ -61,7 +61,7 @@ use auth::AuthMethod;
 pub use pm::add;
 use publish_order::PublishOrderGraph;
 pub use unfurl::deno_json_deps;
-use unfurl::SpecifierUnfurler;
+use unfurl::SpecifierUnfurlerBuilder;
 
 use super::check::TypeChecker;
 
@@ -142,13 +142,35 @@ async fn prepare_publish(
   };
   let file_patterns = deno_json.to_publish_config()?.map(|c| c.files);
 
+  // the diagnostics from parsing the import map are only logged when it's
+  // created, so collect the ones for this package's imports to report them
+  let import_map_specifier = deno_json.specifier.clone();
+  let import_map_diagnostics = if deno_json.is_an_import_map() {
+    import_map::parse_from_value(
+      import_map_specifier.clone(),
+      deno_json.to_import_map_value_from_imports(),
+    )
+    .map(|result| result.diagnostics)
+    .unwrap_or_default()
+  } else {
+    Vec::new()
+  };
+
   let diagnostics_collector = diagnostics_collector.clone();
   let tarball = deno_core::unsync::spawn_blocking(move || {
-    let unfurler = SpecifierUnfurler::new(
-      &mapped_resolver,
-      sloppy_imports_resolver.as_ref(),
-      bare_node_builtins,
-    );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(sloppy_imports_resolver.as_ref())
+      .bare_node_builtins(bare_node_builtins)
+      .validate(true)
+      .report_import_map_diagnostics(
+        &import_map_specifier,
+        &import_map_diagnostics,
+        &mut |diagnostic| {
+          diagnostics_collector
+            .push(PublishDiagnostic::SpecifierUnfurl(diagnostic))
+        },
+      )
+      .build();
     tar::create_gzipped_tarball(
       &dir_path,
       LazyGraphSourceParser::new(&source_cache, &graph),
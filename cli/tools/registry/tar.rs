@@ -230,6 +230,24 @@ fn resolve_content_maybe_unfurling(
         | MediaType::Tsx => {
           // continue
         }
+        MediaType::Unknown if specifier.path().ends_with(".css") => {
+          // css isn't a module, so its references are unfurled textually
+          return match String::from_utf8(data) {
+            Ok(text) => {
+              let mut reporter = |diagnostic| {
+                diagnostics_collector
+                  .push(PublishDiagnostic::SpecifierUnfurl(diagnostic));
+              };
+              log::debug!("Unfurling {}", specifier);
+              Ok(
+                unfurler
+                  .unfurl_css(specifier, &text, &mut reporter)
+                  .into_bytes(),
+              )
+            }
+            Err(err) => Ok(err.into_bytes()),
+          };
+        }
         MediaType::SourceMap
         | MediaType::Unknown
         | MediaType::Json
@@ -241,14 +259,18 @@ fn resolve_content_maybe_unfurling(
       }
 
       let text = String::from_utf8(data)?;
-      deno_ast::parse_module(deno_ast::ParseParams {
-        specifier: specifier.clone(),
-        text_info: deno_ast::SourceTextInfo::from_string(text),
-        media_type,
-        capture_tokens: false,
-        maybe_syntax: None,
-        scope_analysis: false,
-      })?
+      // a parse error is reported as a diagnostic pointing at the syntax
+      // error rather than failing the tarball creation
+      let mut reporter = |diagnostic| {
+        diagnostics_collector
+          .push(PublishDiagnostic::SpecifierUnfurl(diagnostic));
+      };
+      log::debug!("Unfurling {}", specifier);
+      return Ok(
+        unfurler
+          .unfurl_source(specifier, &text, media_type, &mut reporter)
+          .into_bytes(),
+      );
     }
   };
 
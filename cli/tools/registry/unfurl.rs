@@ -1,12 +1,29 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::ops::ControlFlow;
+use std::ops::Deref;
+use std::str::FromStr;
 
+use deno_ast::swc::ast as swc_ast;
+use deno_ast::swc::common::comments::CommentKind;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::MediaType;
 use deno_ast::ParsedSource;
+use deno_ast::SourcePos;
 use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
 use deno_ast::SourceTextInfo;
+use deno_core::anyhow::anyhow;
+use deno_core::error::AnyError;
 use deno_core::serde_json;
 use deno_core::ModuleSpecifier;
+use deno_graph::analyze_deno_types;
 use deno_graph::DefaultModuleAnalyzer;
 use deno_graph::DependencyDescriptor;
 use deno_graph::DynamicTemplatePart;
@@ -15,9 +32,19 @@ use deno_runtime::deno_node::is_builtin_node_module;
 use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
+use deno_semver::package::PackageKind;
+use deno_semver::package::PackageReq;
+use import_map::ImportMap;
+use lazy_regex::lazy_regex;
+use once_cell::sync::Lazy;
+use regex::Regex;
 
+use crate::args::package_json::PackageJsonDepValueParseError;
+use crate::resolver::MappedResolution;
 use crate::resolver::MappedSpecifierResolver;
 use crate::resolver::SloppyImportsResolver;
+use crate::tools::test::is_supported_test_path;
+use crate::util::path::specifier_to_file_path;
 
 pub fn deno_json_deps(
   config: &deno_config::ConfigFile,
@@ -28,12 +55,116 @@ pub fn deno_json_deps(
   values_to_set(values)
 }
 
+/// Gets the dependencies of a deno.json along with those of the external
+/// import map its `importMap` field refers to, which `deno_json_deps`
+/// doesn't read.
+#[cfg(test)]
+pub fn deno_json_deps_with_import_map(
+  config: &deno_config::ConfigFile,
+  import_map_value: &serde_json::Value,
+) -> HashSet<JsrDepPackageReq> {
+  let mut deps = deno_json_deps(config);
+  deps.extend(deps_from_json(import_map_value));
+  deps
+}
+
+/// Gets the dependencies of the raw JSON of a deno.json file, for when it
+/// hasn't been parsed into a config file.
+#[cfg(test)]
+pub fn deps_from_json(value: &serde_json::Value) -> HashSet<JsrDepPackageReq> {
+  let values = imports_values(value.get("imports"))
+    .into_iter()
+    .chain(scope_values(value.get("scopes")));
+  values_to_set(values)
+}
+
+/// Sorts diagnostics by where they're located in the module, rather than
+/// the order the unfurler's passes reported them in. Diagnostics about the
+/// whole module come first.
+#[cfg(test)]
+pub fn sort_diagnostics(diagnostics: &mut [SpecifierUnfurlerDiagnostic]) {
+  diagnostics.sort_by_key(|diagnostic| {
+    diagnostic
+      .source_range()
+      .map(|(text_info, range)| range.start - text_info.range().start)
+      .unwrap_or(0)
+  });
+}
+
+/// Counts the diagnostics by their code, for summarizing the diagnostics of
+/// a package (ex. "3 unanalyzable-dynamic-import, 2 sloppy-import-rewrite").
+#[cfg(test)]
+pub fn tally(
+  diagnostics: &[SpecifierUnfurlerDiagnostic],
+) -> std::collections::BTreeMap<&'static str, usize> {
+  let mut counts = std::collections::BTreeMap::new();
+  for diagnostic in diagnostics {
+    *counts.entry(diagnostic.code()).or_insert(0) += 1;
+  }
+  counts
+}
+
+/// Finds the jsr dependencies that are also depended on through jsr's npm
+/// compatibility layer (ex. `jsr:@std/fs@1` and `npm:@jsr/std__fs@1`),
+/// which would pull in the same code twice. Returns the jsr dependency along
+/// with its npm duplicate.
+#[cfg(test)]
+pub fn detect_jsr_npm_duplicates(
+  deps: &HashSet<JsrDepPackageReq>,
+) -> Vec<(JsrDepPackageReq, JsrDepPackageReq)> {
+  let mut duplicates = Vec::new();
+  for npm_dep in deps.iter().filter(|dep| dep.kind == PackageKind::Npm) {
+    let Some((scope, name)) = npm_dep
+      .req
+      .name
+      .strip_prefix("@jsr/")
+      .and_then(|scope_name| scope_name.split_once("__"))
+    else {
+      continue;
+    };
+    let jsr_name = format!("@{scope}/{name}");
+    for jsr_dep in deps
+      .iter()
+      .filter(|dep| dep.kind == PackageKind::Jsr && dep.req.name == jsr_name)
+    {
+      duplicates.push((jsr_dep.clone(), npm_dep.clone()));
+    }
+  }
+  duplicates.sort();
+  duplicates
+}
+
+/// Finds the dependencies a package uses that aren't declared in its
+/// deno.json (ex. from `deno_json_deps`), so they wouldn't be covered by the
+/// lockfile. A dependency is declared when a dependency on the same package
+/// is, regardless of the version requirement.
+#[cfg(test)]
+pub fn missing_declared_deps(
+  used: &HashSet<JsrDepPackageReq>,
+  declared: &HashSet<JsrDepPackageReq>,
+) -> Vec<JsrDepPackageReq> {
+  let mut missing = used
+    .iter()
+    .filter(|used_dep| {
+      !declared.iter().any(|declared_dep| {
+        declared_dep.kind == used_dep.kind
+          && declared_dep.req.name == used_dep.req.name
+      })
+    })
+    .cloned()
+    .collect::<Vec<_>>();
+  missing.sort();
+  missing
+}
+
 fn imports_values(value: Option<&serde_json::Value>) -> Vec<&String> {
   let Some(obj) = value.and_then(|v| v.as_object()) else {
     return Vec::new();
   };
   let mut items = Vec::with_capacity(obj.len());
   for value in obj.values() {
+    // skip invalid values (ex. numbers or `null`) rather than failing,
+    // since the import map reports them
     if let serde_json::Value::String(value) = value {
       items.push(value);
     }
@@ -57,24 +188,375 @@ fn values_to_set<'a>(
       entries.insert(JsrDepPackageReq::jsr(req_ref.into_inner().req));
     } else if let Ok(req_ref) = NpmPackageReqReference::from_str(value) {
       entries.insert(JsrDepPackageReq::npm(req_ref.into_inner().req));
+    } else if let Some(dep_req) = versionless_dep_req(value) {
+      entries.insert(dep_req);
     }
   }
   entries
 }
 
+/// A package that's depended on with version requirements that no single
+/// version satisfies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepConflict {
+  pub name: String,
+  /// The conflicting dependencies on the package, sorted.
+  pub reqs: Vec<JsrDepPackageReq>,
+}
+
+/// Unions the dependency sets, reporting each package whose version
+/// requirements across the sets don't intersect (ex. `npm:chalk@4` and
+/// `npm:chalk@5`). Dependencies on a jsr and an npm package of the same name
+/// are on different packages, so they never conflict.
+#[cfg(test)]
+pub fn merge_dep_sets(
+  sets: &[HashSet<JsrDepPackageReq>],
+) -> (HashSet<JsrDepPackageReq>, Vec<DepConflict>) {
+  let merged = sets.iter().flatten().cloned().collect::<HashSet<_>>();
+  let mut by_package = HashMap::<_, Vec<&JsrDepPackageReq>>::new();
+  for dep in &merged {
+    by_package
+      .entry((dep.kind, dep.req.name.as_str()))
+      .or_default()
+      .push(dep);
+  }
+  let mut conflicts = by_package
+    .into_iter()
+    .filter(|(_, deps)| {
+      deps.iter().enumerate().any(|(i, a)| {
+        deps[i + 1..]
+          .iter()
+          .any(|b| !a.req.version_req.intersects(&b.req.version_req))
+      })
+    })
+    .map(|((_, name), deps)| {
+      let mut reqs = deps.into_iter().cloned().collect::<Vec<_>>();
+      reqs.sort();
+      DepConflict {
+        name: name.to_string(),
+        reqs,
+      }
+    })
+    .collect::<Vec<_>>();
+  conflicts.sort_by(|a, b| a.reqs.cmp(&b.reqs));
+  (merged, conflicts)
+}
+
+/// Gets a dependency on any version of the package for a `jsr:` or `npm:`
+/// specifier whose version requirement couldn't be parsed.
+fn versionless_dep_req(value: &str) -> Option<JsrDepPackageReq> {
+  let (kind, input) = if let Some(input) = value.strip_prefix("jsr:") {
+    (PackageKind::Jsr, input)
+  } else if let Some(input) = value.strip_prefix("npm:") {
+    (PackageKind::Npm, input)
+  } else {
+    return None;
+  };
+  let input = input.strip_prefix('/').unwrap_or(input);
+  let name_len = if input.starts_with('@') {
+    let scope_len = input.find('/')? + 1;
+    scope_len
+      + input[scope_len..]
+        .find('/')
+        .unwrap_or(input.len() - scope_len)
+  } else {
+    input.find('/').unwrap_or(input.len())
+  };
+  let name = &input[..name_len];
+  let name = match name.rsplit_once('@') {
+    Some((name, _)) if !name.is_empty() => name,
+    _ => name,
+  };
+  let req = PackageReq::from_str(name).ok()?;
+  Some(JsrDepPackageReq { kind, req })
+}
+
+/// The code of a [`SpecifierUnfurlerDiagnostic`], for matching on the kind
+/// of diagnostic without its details.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+  UnanalyzableDynamicImport,
+  BareNodeBuiltin,
+  DisallowedScheme,
+  NonRegistryDependency,
+  SelfImport,
+  TypeScriptImport,
+  CaseMismatch,
+  DeepRelativeImport,
+  ParseError,
+  ImportMapAliasCycle,
+  RewriteProducedInvalidSource,
+  UnresolvedDirectoryImport,
+  EmptySpecifier,
+  SloppyImportRewrite,
+  AmbiguousImportMapMatch,
+  BackslashInSpecifier,
+  CrossExportInternalImport,
+  ImportMapDiagnostic,
+  MissingFile,
+  PossibleTrailingSlashMismatch,
+  NullImportMapEntry,
+  TestFileImport,
+}
+
+impl DiagnosticCode {
+  pub const ALL: &'static [DiagnosticCode] = &[
+    Self::UnanalyzableDynamicImport,
+    Self::BareNodeBuiltin,
+    Self::DisallowedScheme,
+    Self::NonRegistryDependency,
+    Self::SelfImport,
+    Self::TypeScriptImport,
+    Self::CaseMismatch,
+    Self::DeepRelativeImport,
+    Self::ParseError,
+    Self::ImportMapAliasCycle,
+    Self::RewriteProducedInvalidSource,
+    Self::UnresolvedDirectoryImport,
+    Self::EmptySpecifier,
+    Self::SloppyImportRewrite,
+    Self::AmbiguousImportMapMatch,
+    Self::BackslashInSpecifier,
+    Self::CrossExportInternalImport,
+    Self::ImportMapDiagnostic,
+    Self::MissingFile,
+    Self::PossibleTrailingSlashMismatch,
+    Self::NullImportMapEntry,
+    Self::TestFileImport,
+  ];
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::UnanalyzableDynamicImport => "unanalyzable-dynamic-import",
+      Self::BareNodeBuiltin => "bare-node-builtin",
+      Self::DisallowedScheme => "disallowed-scheme",
+      Self::NonRegistryDependency => "non-registry-dependency",
+      Self::SelfImport => "self-import",
+      Self::TypeScriptImport => "typescript-import",
+      Self::CaseMismatch => "case-mismatch",
+      Self::DeepRelativeImport => "deep-relative-import",
+      Self::ParseError => "parse-error",
+      Self::ImportMapAliasCycle => "import-map-alias-cycle",
+      Self::RewriteProducedInvalidSource => "rewrite-produced-invalid-source",
+      Self::UnresolvedDirectoryImport => "unresolved-directory-import",
+      Self::EmptySpecifier => "empty-specifier",
+      Self::SloppyImportRewrite => "sloppy-import",
+      Self::AmbiguousImportMapMatch => "ambiguous-import-map-match",
+      Self::BackslashInSpecifier => "backslash-in-specifier",
+      Self::CrossExportInternalImport => "cross-export-internal-import",
+      Self::ImportMapDiagnostic => "import-map-diagnostic",
+      Self::MissingFile => "missing-file",
+      Self::PossibleTrailingSlashMismatch => "possible-trailing-slash-mismatch",
+      Self::NullImportMapEntry => "null-import-map-entry",
+      Self::TestFileImport => "test-file-import",
+    }
+  }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for DiagnosticCode {
+  type Err = AnyError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::ALL
+      .iter()
+      .find(|code| code.as_str() == s)
+      .copied()
+      .ok_or_else(|| anyhow!("unknown diagnostic code '{}'", s))
+  }
+}
+
 #[derive(Debug, Clone)]
 pub enum SpecifierUnfurlerDiagnostic {
   UnanalyzableDynamicImport {
     specifier: ModuleSpecifier,
     text_info: SourceTextInfo,
     range: SourceRange,
+    /// The source text of the dynamic import's argument.
+    argument_text: String,
+  },
+  BareNodeBuiltin {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  DisallowedScheme {
+    specifier: ModuleSpecifier,
+    resolved: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  NonRegistryDependency {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The scheme of the package.json dependency (ex. `file` or `github`).
+    scheme: String,
+  },
+  SelfImport {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  TypeScriptImport {
+    specifier: ModuleSpecifier,
+    resolved: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  CaseMismatch {
+    specifier: ModuleSpecifier,
+    /// The resolved specifier with the casing the file has on disk.
+    on_disk: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  DeepRelativeImport {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The number of leading `../` segments in the unfurled specifier.
+    depth: usize,
+  },
+  ParseError {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The message of the syntax error.
+    message: String,
+  },
+  ImportMapAliasCycle {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The aliases that were followed, ending with the repeated one.
+    chain: Vec<String>,
+  },
+  RewriteProducedInvalidSource {
+    specifier: ModuleSpecifier,
+    /// The message of the syntax error in the rewritten source.
+    message: String,
+  },
+  UnresolvedDirectoryImport {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  EmptySpecifier {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  SloppyImportRewrite {
+    specifier: ModuleSpecifier,
+    /// The specifier the sloppy import was resolved to.
+    resolved: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  AmbiguousImportMapMatch {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The import map keys that match the specifier.
+    keys: Vec<String>,
+  },
+  BackslashInSpecifier {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+  },
+  CrossExportInternalImport {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The export whose internals are imported.
+    export: ModuleSpecifier,
+  },
+  ImportMapDiagnostic {
+    /// The import map the diagnostic is for.
+    specifier: ModuleSpecifier,
+    message: String,
+  },
+  MissingFile {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The file specifier that doesn't exist.
+    resolved: ModuleSpecifier,
+  },
+  PossibleTrailingSlashMismatch {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The import map key the specifier would match with a trailing slash.
+    key: String,
+  },
+  NullImportMapEntry {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The key of the import map entry with a `null` value.
+    key: String,
+  },
+  TestFileImport {
+    specifier: ModuleSpecifier,
+    text_info: SourceTextInfo,
+    range: SourceRange,
+    /// The test module that's imported.
+    resolved: ModuleSpecifier,
   },
 }
 
 impl SpecifierUnfurlerDiagnostic {
   pub fn code(&self) -> &'static str {
+    self.code_enum().as_str()
+  }
+
+  pub fn code_enum(&self) -> DiagnosticCode {
     match self {
-      Self::UnanalyzableDynamicImport { .. } => "unanalyzable-dynamic-import",
+      Self::UnanalyzableDynamicImport { .. } => {
+        DiagnosticCode::UnanalyzableDynamicImport
+      }
+      Self::BareNodeBuiltin { .. } => DiagnosticCode::BareNodeBuiltin,
+      Self::DisallowedScheme { .. } => DiagnosticCode::DisallowedScheme,
+      Self::NonRegistryDependency { .. } => {
+        DiagnosticCode::NonRegistryDependency
+      }
+      Self::SelfImport { .. } => DiagnosticCode::SelfImport,
+      Self::TypeScriptImport { .. } => DiagnosticCode::TypeScriptImport,
+      Self::CaseMismatch { .. } => DiagnosticCode::CaseMismatch,
+      Self::DeepRelativeImport { .. } => DiagnosticCode::DeepRelativeImport,
+      Self::ParseError { .. } => DiagnosticCode::ParseError,
+      Self::ImportMapAliasCycle { .. } => DiagnosticCode::ImportMapAliasCycle,
+      Self::RewriteProducedInvalidSource { .. } => {
+        DiagnosticCode::RewriteProducedInvalidSource
+      }
+      Self::UnresolvedDirectoryImport { .. } => {
+        DiagnosticCode::UnresolvedDirectoryImport
+      }
+      Self::EmptySpecifier { .. } => DiagnosticCode::EmptySpecifier,
+      Self::SloppyImportRewrite { .. } => DiagnosticCode::SloppyImportRewrite,
+      Self::AmbiguousImportMapMatch { .. } => {
+        DiagnosticCode::AmbiguousImportMapMatch
+      }
+      Self::BackslashInSpecifier { .. } => DiagnosticCode::BackslashInSpecifier,
+      Self::CrossExportInternalImport { .. } => {
+        DiagnosticCode::CrossExportInternalImport
+      }
+      Self::ImportMapDiagnostic { .. } => DiagnosticCode::ImportMapDiagnostic,
+      Self::MissingFile { .. } => DiagnosticCode::MissingFile,
+      Self::PossibleTrailingSlashMismatch { .. } => {
+        DiagnosticCode::PossibleTrailingSlashMismatch
+      }
+      Self::NullImportMapEntry { .. } => DiagnosticCode::NullImportMapEntry,
+      Self::TestFileImport { .. } => DiagnosticCode::TestFileImport,
     }
   }
 
@@ -83,418 +565,6410 @@ impl SpecifierUnfurlerDiagnostic {
       Self::UnanalyzableDynamicImport { .. } => {
         "unable to analyze dynamic import"
       }
+      Self::BareNodeBuiltin { .. } => {
+        "node built-in module imported without the 'node:' prefix"
+      }
+      Self::DisallowedScheme { .. } => {
+        "import of a disallowed specifier scheme"
+      }
+      Self::NonRegistryDependency { .. } => {
+        "import of a package.json dependency that is not from a registry"
+      }
+      Self::SelfImport { .. } => "module imports itself",
+      Self::TypeScriptImport { .. } => "import of a TypeScript module",
+      Self::CaseMismatch { .. } => {
+        "specifier casing does not match the file on disk"
+      }
+      Self::DeepRelativeImport { .. } => {
+        "relative import goes up too many directories"
+      }
+      Self::ParseError { .. } => "unable to parse module for unfurling",
+      Self::ImportMapAliasCycle { .. } => "import map aliases form a cycle",
+      Self::RewriteProducedInvalidSource { .. } => {
+        "unfurling produced source that fails to parse"
+      }
+      Self::UnresolvedDirectoryImport { .. } => {
+        "directory import could not be resolved to a module"
+      }
+      Self::EmptySpecifier { .. } => "empty specifier",
+      Self::SloppyImportRewrite { .. } => "sloppy import",
+      Self::AmbiguousImportMapMatch { .. } => {
+        "specifier matches multiple import map entries"
+      }
+      Self::BackslashInSpecifier { .. } => "backslash in specifier",
+      Self::CrossExportInternalImport { .. } => {
+        "relative import of the internals of another export"
+      }
+      Self::ImportMapDiagnostic { .. } => "invalid import map entry",
+      Self::MissingFile { .. } => {
+        "relative import of a file that does not exist"
+      }
+      Self::PossibleTrailingSlashMismatch { .. } => {
+        "bare specifier only matches an import map key with a trailing slash"
+      }
+      Self::NullImportMapEntry { .. } => {
+        "specifier is blocked by a null import map entry"
+      }
+      Self::TestFileImport { .. } => "import of a test module",
     }
   }
-}
 
-pub struct SpecifierUnfurler<'a> {
-  mapped_resolver: &'a MappedSpecifierResolver,
-  sloppy_imports_resolver: Option<&'a SloppyImportsResolver>,
-  bare_node_builtins: bool,
-}
+  /// Gets the message along with any details about the specific occurrence.
+  pub fn detailed_message(&self) -> Cow<'static, str> {
+    match self {
+      Self::UnanalyzableDynamicImport { argument_text, .. } => {
+        Cow::Owned(format!("{}: {}", self.message(), argument_text))
+      }
+      _ => Cow::Borrowed(self.message()),
+    }
+  }
 
-impl<'a> SpecifierUnfurler<'a> {
-  pub fn new(
-    mapped_resolver: &'a MappedSpecifierResolver,
-    sloppy_imports_resolver: Option<&'a SloppyImportsResolver>,
-    bare_node_builtins: bool,
-  ) -> Self {
-    Self {
-      mapped_resolver,
-      sloppy_imports_resolver,
-      bare_node_builtins,
+  /// Gets the text to replace the diagnostic's range with to fix it, for
+  /// the diagnostics that have an obvious fix.
+  #[cfg(test)]
+  pub fn suggested_fix(&self) -> Option<String> {
+    match self {
+      Self::BareNodeBuiltin {
+        text_info, range, ..
+      } => Some(format!("node:{}", text_info.range_text(range))),
+      Self::SloppyImportRewrite {
+        specifier,
+        resolved,
+        ..
+      } => relative_url(resolved, specifier),
+      Self::CaseMismatch {
+        specifier, on_disk, ..
+      } => relative_url(on_disk, specifier),
+      _ => None,
     }
   }
 
-  fn unfurl_specifier(
-    &self,
-    referrer: &ModuleSpecifier,
-    specifier: &str,
-  ) -> Option<String> {
-    let resolved =
-      if let Ok(resolved) = self.mapped_resolver.resolve(specifier, referrer) {
-        resolved.into_specifier()
-      } else {
-        None
-      };
-    let resolved = match resolved {
-      Some(resolved) => resolved,
-      None if self.bare_node_builtins && is_builtin_node_module(specifier) => {
-        format!("node:{specifier}").parse().unwrap()
+  /// Gets the source text and the range in it that the diagnostic is
+  /// located at, which is `None` for diagnostics about the whole module.
+  #[cfg(test)]
+  fn source_range(&self) -> Option<(&SourceTextInfo, &SourceRange)> {
+    match self {
+      Self::UnanalyzableDynamicImport {
+        text_info, range, ..
       }
-      None => ModuleSpecifier::options()
-        .base_url(Some(referrer))
-        .parse(specifier)
-        .ok()?,
+      | Self::BareNodeBuiltin {
+        text_info, range, ..
+      }
+      | Self::DisallowedScheme {
+        text_info, range, ..
+      }
+      | Self::NonRegistryDependency {
+        text_info, range, ..
+      }
+      | Self::SelfImport {
+        text_info, range, ..
+      }
+      | Self::TypeScriptImport {
+        text_info, range, ..
+      }
+      | Self::CaseMismatch {
+        text_info, range, ..
+      }
+      | Self::DeepRelativeImport {
+        text_info, range, ..
+      }
+      | Self::ParseError {
+        text_info, range, ..
+      }
+      | Self::ImportMapAliasCycle {
+        text_info, range, ..
+      }
+      | Self::UnresolvedDirectoryImport {
+        text_info, range, ..
+      }
+      | Self::EmptySpecifier {
+        text_info, range, ..
+      }
+      | Self::SloppyImportRewrite {
+        text_info, range, ..
+      }
+      | Self::AmbiguousImportMapMatch {
+        text_info, range, ..
+      }
+      | Self::BackslashInSpecifier {
+        text_info, range, ..
+      }
+      | Self::CrossExportInternalImport {
+        text_info, range, ..
+      }
+      | Self::MissingFile {
+        text_info, range, ..
+      }
+      | Self::PossibleTrailingSlashMismatch {
+        text_info, range, ..
+      }
+      | Self::NullImportMapEntry {
+        text_info, range, ..
+      }
+      | Self::TestFileImport {
+        text_info, range, ..
+      } => Some((text_info, range)),
+      Self::RewriteProducedInvalidSource { .. }
+      | Self::ImportMapDiagnostic { .. } => None,
+    }
+  }
+
+  /// Gets the range of the diagnostic as an LSP range, which counts
+  /// characters in UTF-16 code units. Diagnostics about the whole module
+  /// are located at its start.
+  #[cfg(test)]
+  pub fn lsp_range(&self) -> lsp_types::Range {
+    let Some((text_info, range)) = self.source_range() else {
+      return lsp_types::Range::default();
     };
-    // TODO(lucacasonato): this requires integration in deno_graph first
-    // let resolved = if let Ok(specifier) =
-    //   NpmPackageReqReference::from_specifier(&resolved)
-    // {
-    //   if let Some(scope_name) = specifier.req().name.strip_prefix("@jsr/") {
-    //     let (scope, name) = scope_name.split_once("__")?;
-    //     let new_specifier = JsrPackageReqReference::new(PackageReqReference {
-    //       req: PackageReq {
-    //         name: format!("@{scope}/{name}"),
-    //         version_req: specifier.req().version_req.clone(),
-    //       },
-    //       sub_path: specifier.sub_path().map(ToOwned::to_owned),
-    //     })
-    //     .to_string();
-    //     ModuleSpecifier::parse(&new_specifier).unwrap()
-    //   } else {
-    //     resolved
-    //   }
-    // } else {
-    //   resolved
-    // };
-    let resolved =
-      if let Some(sloppy_imports_resolver) = self.sloppy_imports_resolver {
-        sloppy_imports_resolver
-          .resolve(&resolved, deno_graph::source::ResolutionMode::Execution)
-          .as_specifier()
-          .clone()
-      } else {
-        resolved
-      };
-    let relative_resolved = relative_url(&resolved, referrer);
-    if relative_resolved == specifier {
-      None // nothing to unfurl
-    } else {
-      Some(relative_resolved)
+    lsp_types::Range {
+      start: lsp_position(text_info, range.start),
+      end: lsp_position(text_info, range.end),
     }
   }
+}
 
-  /// Attempts to unfurl the dynamic dependency returning `true` on success
-  /// or `false` when the import was not analyzable.
-  fn try_unfurl_dynamic_dep(
-    &self,
-    module_url: &lsp_types::Url,
-    parsed_source: &ParsedSource,
-    dep: &deno_graph::DynamicDependencyDescriptor,
-    text_changes: &mut Vec<deno_ast::TextChange>,
-  ) -> bool {
-    match &dep.argument {
-      deno_graph::DynamicArgument::String(specifier) => {
-        let range = to_range(parsed_source, &dep.argument_range);
-        let maybe_relative_index = parsed_source.text_info().text_str()
-          [range.start..range.end]
-          .find(specifier);
-        let Some(relative_index) = maybe_relative_index else {
-          return true; // always say it's analyzable for a string
-        };
-        let unfurled = self.unfurl_specifier(module_url, specifier);
-        if let Some(unfurled) = unfurled {
-          let start = range.start + relative_index;
-          text_changes.push(deno_ast::TextChange {
-            range: start..start + specifier.len(),
-            new_text: unfurled,
-          });
-        }
-        true
-      }
-      deno_graph::DynamicArgument::Template(parts) => match parts.first() {
-        Some(DynamicTemplatePart::String { value: specifier }) => {
-          // relative doesn't need to be modified
-          let is_relative =
-            specifier.starts_with("./") || specifier.starts_with("../");
-          if is_relative {
-            return true;
-          }
-          if !specifier.ends_with('/') {
-            return false;
-          }
-          let unfurled = self.unfurl_specifier(module_url, specifier);
-          let Some(unfurled) = unfurled else {
-            return true; // nothing to unfurl
-          };
-          let range = to_range(parsed_source, &dep.argument_range);
-          let maybe_relative_index =
-            parsed_source.text_info().text_str()[range.start..].find(specifier);
-          let Some(relative_index) = maybe_relative_index else {
-            return false;
-          };
-          let start = range.start + relative_index;
-          text_changes.push(deno_ast::TextChange {
-            range: start..start + specifier.len(),
-            new_text: unfurled,
-          });
-          true
-        }
-        Some(DynamicTemplatePart::Expr) => {
-          false // failed analyzing
-        }
-        None => {
-          true // ignore
-        }
+#[cfg(test)]
+fn lsp_position(
+  text_info: &SourceTextInfo,
+  pos: SourcePos,
+) -> lsp_types::Position {
+  let line_index = text_info.line_index(pos);
+  let line_start = text_info.line_start(line_index);
+  let line_text = text_info.range_text(&SourceRange::new(line_start, pos));
+  lsp_types::Position {
+    line: line_index as u32,
+    character: line_text.encode_utf16().count() as u32,
+  }
+}
+
+/// Matches the `@ts-types` pragma, which succeeds `@deno-types`.
+static TS_TYPES_RE: Lazy<Regex> =
+  lazy_regex!(r#"(?i)^\s*@ts-types\s*=\s*(?:["']([^"']+)["']|(\S+))"#);
+
+/// Matches the specifier of a JSDoc `@import` tag (ex.
+/// `@import { Foo } from "./foo.js"` or `@import "./foo.js"`).
+static JSDOC_IMPORT_TAG_RE: Lazy<Regex> = lazy_regex!(
+  r#"@import\s+(?:(?:\{[^}]*\}|\*\s*as\s+[\w$]+|[\w$]+)\s+from\s+)?['"]([^'"]+)['"]"#
+);
+
+/// Matches the string form of a CSS `@import` (ex. `@import "./foo.css"`).
+/// The `url()` form is handled by `CSS_URL_RE`.
+static CSS_IMPORT_RE: Lazy<Regex> =
+  lazy_regex!(r#"(?i)@import\s+(?:"([^"]*)"|'([^']*)')"#);
+
+/// Matches a CSS `url()` reference, which may or may not be quoted.
+static CSS_URL_RE: Lazy<Regex> =
+  lazy_regex!(r#"(?i)\burl\(\s*(?:"([^"]*)"|'([^']*)'|([^"'()\s]*))\s*\)"#);
+
+/// How local file specifiers are written after unfurling.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeStyle {
+  /// Relative to the importing module (ex. `./lib/foo.ts`).
+  #[default]
+  DotSlash,
+  /// Absolute from the package root (ex. `/lib/foo.ts`). Falls back to
+  /// `DotSlash` for files outside the package root or when none is set.
+  #[cfg(test)]
+  BareFromRoot,
+  /// The resolved `file://` URL (ex. `file:///dev/pkg/lib/foo.ts`), which
+  /// is for debugging where specifiers resolve to and can't be published.
+  #[cfg(test)]
+  AbsoluteFileUrl,
+}
+
+/// A registry specific rewrite of resolved specifiers, which is applied
+/// before they're made relative.
+pub trait SpecifierTransform {
+  /// Gets the text to use for the resolved specifier or `None` to unfurl
+  /// it normally.
+  fn transform(&self, resolved: &ModuleSpecifier) -> Option<String>;
+}
+
+/// The kinds of dependencies to unfurl, which can be combined with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DependencyKinds(u8);
+
+impl DependencyKinds {
+  pub const STATIC: Self = Self(1 << 0);
+  pub const DYNAMIC: Self = Self(1 << 1);
+  /// Triple slash `path` and `types` references.
+  pub const TS_REFERENCES: Self = Self(1 << 2);
+  /// JSDoc `{import("...")}` types and `@import` tags.
+  pub const JSDOC: Self = Self(1 << 3);
+  pub const JSX_IMPORT_SOURCE: Self = Self(1 << 4);
+  /// `new URL("...", import.meta.url)` expressions.
+  pub const IMPORT_META_URL: Self = Self(1 << 5);
+  pub const ALL: Self = Self((1 << 6) - 1);
+
+  pub fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  /// Gets these kinds without the kinds in `other`.
+  pub fn difference(self, other: Self) -> Self {
+    Self(self.0 & !other.0)
+  }
+}
+
+impl Default for DependencyKinds {
+  fn default() -> Self {
+    Self::ALL
+  }
+}
+
+impl std::ops::BitOr for DependencyKinds {
+  type Output = Self;
+
+  fn bitor(self, rhs: Self) -> Self {
+    Self(self.0 | rhs.0)
+  }
+}
+
+/// Options for unfurling a single module.
+#[derive(Debug, Clone)]
+pub struct UnfurlOptions {
+  /// The directory the module will be written to, which local specifiers
+  /// are made relative to instead of the module's own location.
+  pub output_base: Option<ModuleSpecifier>,
+  /// The kinds of dependencies to unfurl. Defaults to all of them.
+  pub kinds: DependencyKinds,
+  /// Whether to unfurl triple slash `path` and `types` references, for
+  /// packages whose `.d.ts` files are published separately. This is the
+  /// same as leaving `DependencyKinds::TS_REFERENCES` out of `kinds`.
+  /// Defaults to `true`.
+  pub unfurl_type_references: bool,
+}
+
+impl Default for UnfurlOptions {
+  fn default() -> Self {
+    Self {
+      output_base: None,
+      kinds: DependencyKinds::ALL,
+      unfurl_type_references: true,
+    }
+  }
+}
+
+/// The result of unfurling one of a package's modules.
+#[derive(Debug)]
+pub struct FileUnfurlResult {
+  pub specifier: ModuleSpecifier,
+  /// The unfurled source text.
+  pub text: String,
+  pub diagnostics: Vec<SpecifierUnfurlerDiagnostic>,
+}
+
+/// Unfurled modules keyed by their specifier and a hash of their source
+/// text, so a module that hasn't changed since it was last unfurled (ex. in
+/// watch mode) doesn't need to be analyzed again.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct UnfurlCache {
+  entries:
+    HashMap<(ModuleSpecifier, u64), (String, Vec<SpecifierUnfurlerDiagnostic>)>,
+}
+
+#[cfg(test)]
+impl UnfurlCache {
+  pub fn get(
+    &self,
+    specifier: &ModuleSpecifier,
+    source_text: &str,
+  ) -> Option<(&str, &[SpecifierUnfurlerDiagnostic])> {
+    let key = (
+      specifier.clone(),
+      crate::cache::FastInsecureHasher::hash(source_text),
+    );
+    self
+      .entries
+      .get(&key)
+      .map(|(text, diagnostics)| (text.as_str(), diagnostics.as_slice()))
+  }
+
+  pub fn insert(
+    &mut self,
+    specifier: ModuleSpecifier,
+    source_text: &str,
+    unfurled_text: String,
+    diagnostics: Vec<SpecifierUnfurlerDiagnostic>,
+  ) {
+    let key = (
+      specifier,
+      crate::cache::FastInsecureHasher::hash(source_text),
+    );
+    self.entries.insert(key, (unfurled_text, diagnostics));
+  }
+}
+
+/// A resolver that's either borrowed by the unfurler or shared with it.
+enum ResolverRef<'a, T> {
+  Borrowed(&'a T),
+  #[cfg(test)]
+  Owned(std::sync::Arc<T>),
+}
+
+impl<'a, T> Deref for ResolverRef<'a, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    match self {
+      ResolverRef::Borrowed(resolver) => resolver,
+      #[cfg(test)]
+      ResolverRef::Owned(resolver) => resolver,
+    }
+  }
+}
+
+pub struct SpecifierUnfurler<'a> {
+  mapped_resolver: ResolverRef<'a, MappedSpecifierResolver>,
+  sloppy_imports_resolver: Option<ResolverRef<'a, SloppyImportsResolver>>,
+  resolve_sloppy_imports: bool,
+  bare_node_builtins: bool,
+  allowed_schemes: Option<HashSet<String>>,
+  workspace_members: HashMap<ModuleSpecifier, JsrPackageReqReference>,
+  #[cfg(test)]
+  relative_style: RelativeStyle,
+  #[cfg(test)]
+  package_root: Option<ModuleSpecifier>,
+  warn_typescript_imports: bool,
+  max_relative_depth: Option<usize>,
+  exports: Vec<ModuleSpecifier>,
+  report_only: bool,
+  validate: bool,
+  ignored_codes: HashSet<&'static str>,
+  sloppy_imports_are_errors: bool,
+  sloppy_extension_preference: Option<Vec<MediaType>>,
+  specifier_transform: Option<&'a dyn SpecifierTransform>,
+  warn_ambiguous_import_map_matches: bool,
+  check_file_existence: bool,
+  std_aliases: bool,
+  normalize_quotes: Option<char>,
+  canonicalize_paths: bool,
+}
+
+/// Information about what was unfurled in a module.
+#[derive(Debug, Default)]
+pub struct UnfurlStats {
+  /// The node builtins that bare specifiers were rewritten to, without
+  /// their submodule (ex. `fs` for `fs/promises`).
+  pub node_builtins: HashSet<String>,
+}
+
+/// Records which import map entries were used while unfurling the modules
+/// of a package, so that the unused ones can be reported.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct ImportMapUsageTracker {
+  used: HashSet<String>,
+}
+
+#[cfg(test)]
+impl ImportMapUsageTracker {
+  pub fn record_used(&mut self, key: &str) {
+    self.used.insert(key.to_string());
+  }
+
+  /// Gets the keys of the import map entries that were never used.
+  pub fn unused(&self, import_map: &ImportMap) -> Vec<String> {
+    import_map
+      .imports()
+      .entries()
+      .chain(
+        import_map
+          .scopes()
+          .flat_map(|scope| scope.imports.entries()),
+      )
+      .filter(|entry| !self.used.contains(entry.raw_key))
+      .map(|entry| entry.raw_key.to_string())
+      .collect()
+  }
+}
+
+/// Builds a `SpecifierUnfurler`, defaulting every option that isn't set.
+pub struct SpecifierUnfurlerBuilder<'a> {
+  unfurler: SpecifierUnfurler<'a>,
+}
+
+impl<'a> SpecifierUnfurlerBuilder<'a> {
+  pub fn new(mapped_resolver: &'a MappedSpecifierResolver) -> Self {
+    Self::with_mapped_resolver(ResolverRef::Borrowed(mapped_resolver))
+  }
+
+  fn with_mapped_resolver(
+    mapped_resolver: ResolverRef<'a, MappedSpecifierResolver>,
+  ) -> Self {
+    Self {
+      unfurler: SpecifierUnfurler {
+        mapped_resolver,
+        sloppy_imports_resolver: None,
+        resolve_sloppy_imports: true,
+        bare_node_builtins: false,
+        allowed_schemes: None,
+        workspace_members: HashMap::new(),
+        #[cfg(test)]
+        relative_style: RelativeStyle::default(),
+        #[cfg(test)]
+        package_root: None,
+        warn_typescript_imports: false,
+        max_relative_depth: None,
+        exports: Vec::new(),
+        report_only: false,
+        validate: false,
+        ignored_codes: HashSet::new(),
+        sloppy_imports_are_errors: false,
+        sloppy_extension_preference: None,
+        specifier_transform: None,
+        warn_ambiguous_import_map_matches: false,
+        check_file_existence: false,
+        std_aliases: false,
+        normalize_quotes: None,
+        canonicalize_paths: false,
       },
-      deno_graph::DynamicArgument::Expr => {
-        false // failed analyzing
-      }
     }
   }
 
-  pub fn unfurl(
+  pub fn sloppy_imports_resolver(
+    mut self,
+    sloppy_imports_resolver: Option<&'a SloppyImportsResolver>,
+  ) -> Self {
+    self.unfurler.sloppy_imports_resolver =
+      sloppy_imports_resolver.map(ResolverRef::Borrowed);
+    self
+  }
+
+  /// Whether to rewrite specifiers using the sloppy imports resolver when
+  /// one is provided, which includes fixing their casing to match the file
+  /// on disk. Defaults to `true`.
+  #[cfg(test)]
+  pub fn resolve_sloppy_imports(
+    mut self,
+    resolve_sloppy_imports: bool,
+  ) -> Self {
+    self.unfurler.resolve_sloppy_imports = resolve_sloppy_imports;
+    self
+  }
+
+  pub fn bare_node_builtins(mut self, bare_node_builtins: bool) -> Self {
+    self.unfurler.bare_node_builtins = bare_node_builtins;
+    self
+  }
+
+  /// Restricts the schemes that resolved specifiers may have, reporting a
+  /// diagnostic for any others. `None` allows every scheme.
+  #[cfg(test)]
+  pub fn allowed_schemes(
+    mut self,
+    allowed_schemes: Option<HashSet<String>>,
+  ) -> Self {
+    self.unfurler.allowed_schemes = allowed_schemes;
+    self
+  }
+
+  /// Maps local modules of workspace members to the jsr specifier that
+  /// should be used for them in the published code.
+  #[cfg(test)]
+  pub fn workspace_members(
+    mut self,
+    workspace_members: HashMap<ModuleSpecifier, JsrPackageReqReference>,
+  ) -> Self {
+    self.unfurler.workspace_members = workspace_members;
+    self
+  }
+
+  #[cfg(test)]
+  pub fn relative_style(mut self, relative_style: RelativeStyle) -> Self {
+    self.unfurler.relative_style = relative_style;
+    self
+  }
+
+  /// The directory of the package, which `RelativeStyle::BareFromRoot`
+  /// specifiers are written relative to.
+  #[cfg(test)]
+  pub fn package_root(mut self, mut package_root: ModuleSpecifier) -> Self {
+    if !package_root.path().ends_with('/') {
+      let path = format!("{}/", package_root.path());
+      package_root.set_path(&path);
+    }
+    self.unfurler.package_root = Some(package_root);
+    self
+  }
+
+  /// Whether to report imports of local TypeScript modules, which plain
+  /// JavaScript consumers can't use without a build step. Defaults to
+  /// `false`.
+  #[cfg(test)]
+  pub fn warn_typescript_imports(
+    mut self,
+    warn_typescript_imports: bool,
+  ) -> Self {
+    self.unfurler.warn_typescript_imports = warn_typescript_imports;
+    self
+  }
+
+  /// The most leading `../` segments an unfurled relative specifier may
+  /// have before a diagnostic is reported. `None` allows any depth.
+  #[cfg(test)]
+  pub fn max_relative_depth(
+    mut self,
+    max_relative_depth: Option<usize>,
+  ) -> Self {
+    self.unfurler.max_relative_depth = max_relative_depth;
+    self
+  }
+
+  /// The entrypoints of the package's exports. Each export owns the
+  /// directory its entrypoint is in, and relative imports of modules in
+  /// another export's directory, other than its entrypoint, are reported.
+  #[cfg(test)]
+  pub fn exports(mut self, exports: Vec<ModuleSpecifier>) -> Self {
+    self.unfurler.exports = exports;
+    self
+  }
+
+  /// Whether to only report diagnostics and return the source unchanged,
+  /// for validating a package without unfurling it. Defaults to `false`.
+  #[cfg(test)]
+  pub fn report_only(mut self, report_only: bool) -> Self {
+    self.unfurler.report_only = report_only;
+    self
+  }
+
+  /// Whether to check that the unfurled source still parses, keeping the
+  /// original source when it doesn't. Defaults to `false`.
+  pub fn validate(mut self, validate: bool) -> Self {
+    self.unfurler.validate = validate;
+    self
+  }
+
+  /// The codes of the diagnostics that shouldn't be reported.
+  #[cfg(test)]
+  pub fn ignored_codes(mut self, ignored_codes: HashSet<&'static str>) -> Self {
+    self.unfurler.ignored_codes = ignored_codes;
+    self
+  }
+
+  /// The order of the extensions to try when a sloppy import without an
+  /// extension matches more than one file (ex. both `b.ts` and `b.js`).
+  /// Defaults to the sloppy imports resolver's order, which prefers
+  /// TypeScript files.
+  #[cfg(test)]
+  pub fn sloppy_extension_preference(
+    mut self,
+    preference: Option<Vec<MediaType>>,
+  ) -> Self {
+    self.unfurler.sloppy_extension_preference = preference;
+    self
+  }
+
+  /// Whether to check that relative imports resolve to files that exist,
+  /// reporting a `MissingFile` error otherwise. This uses the file system of
+  /// the sloppy imports resolver, so it requires one. Defaults to `false`.
+  #[cfg(test)]
+  pub fn check_file_existence(mut self, check_file_existence: bool) -> Self {
+    self.unfurler.check_file_existence = check_file_existence;
+    self
+  }
+
+  /// Whether to report sloppy imports that get rewritten as errors, so that
+  /// publishing fails until the source is explicit. Defaults to `false`.
+  #[cfg(test)]
+  pub fn sloppy_imports_are_errors(
+    mut self,
+    sloppy_imports_are_errors: bool,
+  ) -> Self {
+    self.unfurler.sloppy_imports_are_errors = sloppy_imports_are_errors;
+    self
+  }
+
+  #[cfg(test)]
+  pub fn specifier_transform(
+    mut self,
+    specifier_transform: Option<&'a dyn SpecifierTransform>,
+  ) -> Self {
+    self.unfurler.specifier_transform = specifier_transform;
+    self
+  }
+
+  /// Whether to unfurl `@std/` bare specifiers the import map doesn't
+  /// resolve to jsr (ex. `@std/fs/mod.ts` to `jsr:@std/fs/mod.ts`), for
+  /// packages published without an import map. Defaults to `false`.
+  #[cfg(test)]
+  pub fn std_aliases(mut self, std_aliases: bool) -> Self {
+    self.unfurler.std_aliases = std_aliases;
+    self
+  }
+
+  /// The quote (`"` or `'`) to rewrite the quotes of unfurled string
+  /// specifiers with. Specifiers that aren't rewritten keep their quotes.
+  /// Defaults to `None`, which keeps the original quotes.
+  #[cfg(test)]
+  pub fn normalize_quotes(mut self, normalize_quotes: Option<char>) -> Self {
+    self.unfurler.normalize_quotes = normalize_quotes;
+    self
+  }
+
+  /// Whether to resolve the symlinks of local paths before making them
+  /// relative, so the unfurled specifiers are the same whether a module is
+  /// reached through a symlink or its real path. This uses the file system
+  /// of the sloppy imports resolver, so it requires one. Defaults to `false`.
+  #[cfg(test)]
+  pub fn canonicalize_paths(mut self, canonicalize_paths: bool) -> Self {
+    self.unfurler.canonicalize_paths = canonicalize_paths;
+    self
+  }
+
+  /// Whether to report specifiers that match more than one import map
+  /// entry (ex. both `lib/` and `lib/foo.ts`). Defaults to `false`.
+  #[cfg(test)]
+  pub fn warn_ambiguous_import_map_matches(
+    mut self,
+    warn_ambiguous_import_map_matches: bool,
+  ) -> Self {
+    self.unfurler.warn_ambiguous_import_map_matches =
+      warn_ambiguous_import_map_matches;
+    self
+  }
+
+  /// Reports the diagnostics from parsing the import map, which would
+  /// otherwise be dropped even though the entries they're about won't
+  /// resolve as expected.
+  pub fn report_import_map_diagnostics(
+    self,
+    import_map_specifier: &ModuleSpecifier,
+    diagnostics: &[import_map::ImportMapDiagnostic],
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> Self {
+    for diagnostic in diagnostics {
+      diagnostic_reporter(SpecifierUnfurlerDiagnostic::ImportMapDiagnostic {
+        specifier: import_map_specifier.clone(),
+        message: diagnostic.to_string(),
+      });
+    }
+    self
+  }
+
+  pub fn build(self) -> SpecifierUnfurler<'a> {
+    self.unfurler
+  }
+}
+
+impl<'a> SpecifierUnfurler<'a> {
+  #[cfg(test)]
+  pub fn new(
+    mapped_resolver: &'a MappedSpecifierResolver,
+    sloppy_imports_resolver: Option<&'a SloppyImportsResolver>,
+    bare_node_builtins: bool,
+  ) -> Self {
+    SpecifierUnfurlerBuilder::new(mapped_resolver)
+      .sloppy_imports_resolver(sloppy_imports_resolver)
+      .bare_node_builtins(bare_node_builtins)
+      .build()
+  }
+
+  /// Creates an unfurler that owns its resolvers so that it can be stored
+  /// without borrowing them.
+  #[cfg(test)]
+  pub fn new_owned(
+    mapped_resolver: std::sync::Arc<MappedSpecifierResolver>,
+    sloppy_imports_resolver: Option<std::sync::Arc<SloppyImportsResolver>>,
+    bare_node_builtins: bool,
+  ) -> SpecifierUnfurler<'static> {
+    let mut builder = SpecifierUnfurlerBuilder::with_mapped_resolver(
+      ResolverRef::Owned(mapped_resolver),
+    );
+    builder.unfurler.sloppy_imports_resolver =
+      sloppy_imports_resolver.map(ResolverRef::Owned);
+    builder.bare_node_builtins(bare_node_builtins).build()
+  }
+
+  /// Resolves a single specifier the same way it would be unfurled within a
+  /// module, returning `None` when it would be left as-is. Diagnostics are
+  /// not reported.
+  ///
+  /// ```nocompile
+  /// let unfurled = unfurler.resolve_one(&referrer, "lib/foo.ts");
+  /// assert_eq!(unfurled.as_deref(), Some("./lib/foo.ts"));
+  /// ```
+  #[cfg(test)]
+  pub fn resolve_one(
     &self,
-    url: &ModuleSpecifier,
-    parsed_source: &ParsedSource,
+    referrer: &ModuleSpecifier,
+    specifier: &str,
+  ) -> Option<String> {
+    let text_info = SourceTextInfo::from_string(specifier.to_string());
+    let range = text_info.range();
+    let range = SourceRange::new(range.start.as_source_pos(), range.end);
+    self.unfurl_specifier(
+      referrer,
+      specifier,
+      &text_info,
+      range,
+      None,
+      &mut |_| {},
+      &mut |_, _| {},
+    )
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn unfurl_specifier(
+    &self,
+    referrer: &ModuleSpecifier,
+    specifier: &str,
+    text_info: &SourceTextInfo,
+    range: SourceRange,
+    output_base: Option<&ModuleSpecifier>,
     diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
-  ) -> String {
-    let mut text_changes = Vec::new();
-    let module_info = DefaultModuleAnalyzer::module_info(parsed_source);
-    let analyze_specifier =
-      |specifier: &str,
-       range: &deno_graph::PositionRange,
-       text_changes: &mut Vec<deno_ast::TextChange>| {
-        if let Some(unfurled) = self.unfurl_specifier(url, specifier) {
-          text_changes.push(deno_ast::TextChange {
-            range: to_range(parsed_source, range),
-            new_text: unfurled,
-          });
+    resolved_reporter: &mut dyn FnMut(Option<&str>, &ModuleSpecifier),
+  ) -> Option<String> {
+    if specifier.trim().is_empty() {
+      // this would otherwise resolve to the referrer itself
+      diagnostic_reporter(SpecifierUnfurlerDiagnostic::EmptySpecifier {
+        specifier: referrer.clone(),
+        text_info: text_info.clone(),
+        range,
+      });
+      return None;
+    }
+    if specifier
+      .get(..5)
+      .is_some_and(|scheme| scheme.eq_ignore_ascii_case("node:"))
+    {
+      // already an explicit node specifier, so there's nothing to unfurl.
+      // Schemes are case insensitive, so this has to check `NODE:` too,
+      // while the checks below use the normalized `resolved.scheme()`
+      return None;
+    }
+    let normalized_specifier;
+    let specifier = if (specifier.starts_with('.')
+      || specifier.starts_with('/'))
+      && specifier.contains('\\')
+    {
+      // windows style paths aren't valid in ESM, but they're common enough
+      // that we rewrite them to work anyway
+      diagnostic_reporter(SpecifierUnfurlerDiagnostic::BackslashInSpecifier {
+        specifier: referrer.clone(),
+        text_info: text_info.clone(),
+        range,
+      });
+      normalized_specifier = specifier.replace('\\', "/");
+      normalized_specifier.as_str()
+    } else {
+      specifier
+    };
+    if self.warn_ambiguous_import_map_matches {
+      if let Some(import_map) = self.mapped_resolver.maybe_import_map() {
+        let keys = matching_import_map_keys(import_map, specifier, referrer);
+        if keys.len() > 1 {
+          diagnostic_reporter(
+            SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch {
+              specifier: referrer.clone(),
+              text_info: text_info.clone(),
+              range,
+              keys: keys.into_iter().map(ToOwned::to_owned).collect(),
+            },
+          );
         }
-      };
-    for dep in &module_info.dependencies {
-      match dep {
-        DependencyDescriptor::Static(dep) => {
-          analyze_specifier(
-            &dep.specifier,
-            &dep.specifier_range,
-            &mut text_changes,
+      }
+    }
+    // the key of the import map entry the specifier was resolved with
+    let mut import_map_key = None;
+    // import maps can remap full URLs too (ex. a `https://deno.land/std`
+    // module to a `jsr:` specifier)
+    let resolved = match self.mapped_resolver.resolve(specifier, referrer) {
+      // this is only `None` when there's no import map and no package.json
+      // dependency matched, since a matching import map entry always
+      // produces a specifier and a failed match is an error, so the
+      // fallthrough below can't mask a partial resolution
+      Ok(resolution) => {
+        if let MappedResolution::ImportMap(resolved) = &resolution {
+          import_map_key =
+            self
+              .mapped_resolver
+              .maybe_import_map()
+              .and_then(|import_map| {
+                resolved_import_map_key(
+                  import_map, specifier, referrer, resolved,
+                )
+              });
+        }
+        resolution.into_specifier()
+      }
+      Err(_) if self.import_map_alias(specifier, referrer).is_some() => {
+        match self.resolve_import_map_alias(specifier, referrer) {
+          Ok(resolved) => resolved,
+          Err(chain) => {
+            diagnostic_reporter(
+              SpecifierUnfurlerDiagnostic::ImportMapAliasCycle {
+                specifier: referrer.clone(),
+                text_info: text_info.clone(),
+                range,
+                chain,
+              },
+            );
+            return None;
+          }
+        }
+      }
+      // the import map takes precedence over the built-in aliases
+      Err(_) if self.std_alias(specifier).is_some() => {
+        self.std_alias(specifier)
+      }
+      Err(_) => {
+        if let Some(entry) =
+          self
+            .mapped_resolver
+            .maybe_import_map()
+            .and_then(|import_map| {
+              matched_import_map_entry(import_map, specifier, referrer)
+            })
+        {
+          if entry.value.is_none() {
+            // resolving this relative to the referrer would hide that the
+            // import map blocks it
+            diagnostic_reporter(
+              SpecifierUnfurlerDiagnostic::NullImportMapEntry {
+                specifier: referrer.clone(),
+                text_info: text_info.clone(),
+                range,
+                key: entry.raw_key.to_string(),
+              },
+            );
+            return None;
+          }
+        }
+        if let Some(PackageJsonDepValueParseError::Unsupported { scheme }) =
+          self.mapped_resolver.package_json_dep_error(specifier)
+        {
+          // git and file dependencies can't be installed by consumers of
+          // the published package, so leave the import as-is
+          diagnostic_reporter(
+            SpecifierUnfurlerDiagnostic::NonRegistryDependency {
+              specifier: referrer.clone(),
+              text_info: text_info.clone(),
+              range,
+              scheme: scheme.clone(),
+            },
           );
+          return None;
         }
-        DependencyDescriptor::Dynamic(dep) => {
-          let success = self.try_unfurl_dynamic_dep(
-            url,
-            parsed_source,
-            dep,
-            &mut text_changes,
+        if let Some(key) =
+          self
+            .mapped_resolver
+            .maybe_import_map()
+            .and_then(|import_map| {
+              trailing_slash_import_map_key(import_map, specifier, referrer)
+            })
+        {
+          diagnostic_reporter(
+            SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch {
+              specifier: referrer.clone(),
+              text_info: text_info.clone(),
+              range,
+              key: key.to_string(),
+            },
           );
+        }
+        None
+      }
+    };
+    let resolved = match resolved {
+      Some(resolved) => resolved,
+      // this includes submodules like `fs/promises`
+      None if is_builtin_node_module(specifier) => {
+        if self.bare_node_builtins {
+          format!("node:{specifier}").parse().unwrap()
+        } else {
+          // resolving this relative to the referrer would ship a broken
+          // import, so leave it as-is and let the author know
+          diagnostic_reporter(SpecifierUnfurlerDiagnostic::BareNodeBuiltin {
+            specifier: referrer.clone(),
+            text_info: text_info.clone(),
+            range,
+          });
+          return None;
+        }
+      }
+      // bare specifiers that aren't mapped are resolved relative to the
+      // referrer, the way a `baseUrl` would (ex. `foo.ts` to `./foo.ts`)
+      None => ModuleSpecifier::options()
+        .base_url(Some(referrer))
+        .parse(specifier)
+        .ok()?,
+    };
+    // a directory can't be imported once published, so point the import at
+    // the directory's entrypoint instead
+    let resolved = if matches!(specifier, "." | ".." | "./" | "../") {
+      match self
+        .sloppy_imports_resolver
+        .as_deref()
+        .and_then(|resolver| resolver.resolve_directory_entrypoint(&resolved))
+      {
+        Some(entrypoint) => entrypoint,
+        None => {
+          diagnostic_reporter(
+            SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport {
+              specifier: referrer.clone(),
+              text_info: text_info.clone(),
+              range,
+            },
+          );
+          return None;
+        }
+      }
+    } else {
+      resolved
+    };
+    // TODO(lucacasonato): this requires integration in deno_graph first
+    // let resolved = if let Ok(specifier) =
+    //   NpmPackageReqReference::from_specifier(&resolved)
+    // {
+    //   if let Some(scope_name) = specifier.req().name.strip_prefix("@jsr/") {
+    //     let (scope, name) = scope_name.split_once("__")?;
+    //     let new_specifier = JsrPackageReqReference::new(PackageReqReference {
+    //       req: PackageReq {
+    //         name: format!("@{scope}/{name}"),
+    //         version_req: specifier.req().version_req.clone(),
+    //       },
+    //       sub_path: specifier.sub_path().map(ToOwned::to_owned),
+    //     })
+    //     .to_string();
+    //     ModuleSpecifier::parse(&new_specifier).unwrap()
+    //   } else {
+    //     resolved
+    //   }
+    // } else {
+    //   resolved
+    // };
+    let resolved = if let Some(sloppy_imports_resolver) = self
+      .sloppy_imports_resolver
+      .as_deref()
+      .filter(|_| self.resolve_sloppy_imports)
+    {
+      let sloppy_resolved = self
+        .sloppy_extension_preference
+        .as_ref()
+        .and_then(|preference| {
+          sloppy_imports_resolver
+            .resolve_with_extension_preference(&resolved, preference)
+        })
+        .unwrap_or_else(|| {
+          sloppy_imports_resolver
+            .resolve(&resolved, deno_graph::source::ResolutionMode::Execution)
+            .as_specifier()
+            .clone()
+        });
+      if self.sloppy_imports_are_errors && sloppy_resolved != resolved {
+        diagnostic_reporter(SpecifierUnfurlerDiagnostic::SloppyImportRewrite {
+          specifier: referrer.clone(),
+          resolved: sloppy_resolved.clone(),
+          text_info: text_info.clone(),
+          range,
+        });
+      }
+      sloppy_resolved
+    } else {
+      resolved
+    };
+    // import map values can have a trailing slash even when they point at a
+    // file, which wouldn't be a valid specifier for it
+    let resolved = match self.sloppy_imports_resolver.as_deref() {
+      Some(resolver)
+        if resolved.scheme() == "file" && resolved.path().ends_with('/') =>
+      {
+        let mut without_slash = resolved.clone();
+        let path = resolved.path().trim_end_matches('/').to_string();
+        without_slash.set_path(&path);
+        if resolver.is_file(&without_slash) {
+          without_slash
+        } else {
+          resolved
+        }
+      }
+      _ => resolved,
+    };
+    // use the casing of the file on disk so the import still works on case
+    // sensitive file systems
+    let resolved = match self
+      .sloppy_imports_resolver
+      .as_deref()
+      .filter(|_| self.resolve_sloppy_imports && resolved.scheme() == "file")
+      .and_then(|resolver| resolver.resolve_on_disk_casing(&resolved))
+    {
+      Some(on_disk) if on_disk != resolved => {
+        diagnostic_reporter(SpecifierUnfurlerDiagnostic::CaseMismatch {
+          specifier: referrer.clone(),
+          on_disk: on_disk.clone(),
+          text_info: text_info.clone(),
+          range,
+        });
+        on_disk
+      }
+      _ => resolved,
+    };
+    if self.check_file_existence
+      && resolved.scheme() == "file"
+      && (specifier.starts_with("./") || specifier.starts_with("../"))
+    {
+      if let Some(resolver) = self.sloppy_imports_resolver.as_deref() {
+        if !resolver.is_file(&resolved) {
+          diagnostic_reporter(SpecifierUnfurlerDiagnostic::MissingFile {
+            specifier: referrer.clone(),
+            text_info: text_info.clone(),
+            range,
+            resolved: resolved.clone(),
+          });
+        }
+      }
+    }
+    // tests aren't meant to be shipped to consumers, though importing test
+    // helpers from another test is fine
+    if resolved.scheme() == "file"
+      && (specifier.starts_with("./") || specifier.starts_with("../"))
+      && is_test_module(&resolved)
+      && !is_test_module(referrer)
+    {
+      diagnostic_reporter(SpecifierUnfurlerDiagnostic::TestFileImport {
+        specifier: referrer.clone(),
+        text_info: text_info.clone(),
+        range,
+        resolved: resolved.clone(),
+      });
+    }
+    // local workspace members are published on their own, so import them
+    // from jsr rather than relatively
+    let resolved = match self
+      .workspace_members
+      .get(&resolved)
+      .and_then(|req_ref| ModuleSpecifier::parse(&req_ref.to_string()).ok())
+    {
+      Some(jsr_specifier) => jsr_specifier,
+      None => resolved,
+    };
+    resolved_reporter(import_map_key, &resolved);
+    if resolved == *referrer {
+      diagnostic_reporter(SpecifierUnfurlerDiagnostic::SelfImport {
+        specifier: referrer.clone(),
+        text_info: text_info.clone(),
+        range,
+      });
+    }
+    if specifier.starts_with("./") || specifier.starts_with("../") {
+      if let Some(export) =
+        self.cross_export_internal_import(referrer, &resolved)
+      {
+        diagnostic_reporter(
+          SpecifierUnfurlerDiagnostic::CrossExportInternalImport {
+            specifier: referrer.clone(),
+            text_info: text_info.clone(),
+            range,
+            export: export.clone(),
+          },
+        );
+      }
+    }
+    if self.warn_typescript_imports
+      && resolved.scheme() == "file"
+      && [".ts", ".tsx", ".mts", ".cts"]
+        .iter()
+        .any(|ext| resolved.path().ends_with(ext))
+    {
+      diagnostic_reporter(SpecifierUnfurlerDiagnostic::TypeScriptImport {
+        specifier: referrer.clone(),
+        resolved: resolved.clone(),
+        text_info: text_info.clone(),
+        range,
+      });
+    }
+    if let Some(allowed_schemes) = &self.allowed_schemes {
+      if !allowed_schemes.contains(resolved.scheme()) {
+        diagnostic_reporter(SpecifierUnfurlerDiagnostic::DisallowedScheme {
+          specifier: referrer.clone(),
+          resolved: resolved.clone(),
+          text_info: text_info.clone(),
+          range,
+        });
+      }
+    }
+    if let Some(transformed) = self
+      .specifier_transform
+      .and_then(|transform| transform.transform(&resolved))
+    {
+      return (transformed != specifier).then_some(transformed);
+    }
+    // leave the specifier as-is when it can't be made relative
+    let relative_resolved =
+      self.relative_specifier(&resolved, output_base.unwrap_or(referrer))?;
+    if let Some(max_relative_depth) = self.max_relative_depth {
+      let depth = relative_depth(&relative_resolved);
+      if depth > max_relative_depth {
+        diagnostic_reporter(SpecifierUnfurlerDiagnostic::DeepRelativeImport {
+          specifier: referrer.clone(),
+          text_info: text_info.clone(),
+          range,
+          depth,
+        });
+      }
+    }
+    // this is also where identity import map entries end up, since each
+    // entry is resolved once (ex. `"npm:chalk@5": "npm:chalk@5"` resolves to
+    // the same specifier, so the import is left as-is without a diagnostic)
+    if relative_resolved == specifier {
+      None // nothing to unfurl
+    } else {
+      Some(relative_resolved)
+    }
+  }
+
+  /// Gets the prefix to use for a template's `name/` head when `name` is
+  /// mapped to an npm or jsr package without a `name/` entry of its own
+  /// (ex. `express/` to `npm:express@5/`).
+  fn package_alias_prefix(
+    &self,
+    referrer: &ModuleSpecifier,
+    specifier: &str,
+  ) -> Option<String> {
+    let name = specifier.strip_suffix('/')?;
+    if let Ok(
+      MappedResolution::ImportMap(_) | MappedResolution::PackageJson(_),
+    ) = self.mapped_resolver.resolve(specifier, referrer)
+    {
+      return None; // the prefix resolves on its own
+    }
+    let resolved = self
+      .mapped_resolver
+      .resolve(name, referrer)
+      .ok()?
+      .into_specifier()?;
+    matches!(resolved.scheme(), "npm" | "jsr").then(|| format!("{resolved}/"))
+  }
+
+  /// Gets the jsr specifier for a `@std/` bare specifier when `std_aliases`
+  /// is enabled.
+  fn std_alias(&self, specifier: &str) -> Option<ModuleSpecifier> {
+    if !self.std_aliases || !specifier.starts_with("@std/") {
+      return None;
+    }
+    let jsr_specifier = format!("jsr:{specifier}");
+    // ensure it's a valid package name before unfurling it
+    JsrPackageReqReference::from_str(&jsr_specifier).ok()?;
+    ModuleSpecifier::parse(&jsr_specifier).ok()
+  }
+
+  /// Gets the bare specifier an import map entry maps the specifier to.
+  /// The import map itself can't resolve these since its values must be
+  /// URLs or paths.
+  fn import_map_alias(
+    &self,
+    specifier: &str,
+    referrer: &ModuleSpecifier,
+  ) -> Option<String> {
+    let import_map = self.mapped_resolver.maybe_import_map()?;
+    let entry = import_map
+      .entries_for_referrer(referrer)
+      .find(|entry| entry.key == specifier)?;
+    match entry.value {
+      Some(_) => None,
+      None => entry.raw_value.map(ToOwned::to_owned),
+    }
+  }
+
+  /// Follows a chain of import map aliases until one resolves, erroring
+  /// with the followed aliases when they form a cycle.
+  fn resolve_import_map_alias(
+    &self,
+    specifier: &str,
+    referrer: &ModuleSpecifier,
+  ) -> Result<Option<ModuleSpecifier>, Vec<String>> {
+    let mut chain = vec![specifier.to_string()];
+    while let Some(alias) =
+      self.import_map_alias(chain.last().unwrap(), referrer)
+    {
+      let is_cycle = chain.contains(&alias);
+      chain.push(alias);
+      if is_cycle {
+        return Err(chain);
+      }
+      let alias = chain.last().unwrap();
+      if let Ok(resolved) = self.mapped_resolver.resolve(alias, referrer) {
+        return Ok(resolved.into_specifier());
+      }
+    }
+    Ok(None)
+  }
+
+  /// Gets the export whose directory the resolved module is in when that
+  /// differs from the referrer's export and the module isn't its entrypoint.
+  fn cross_export_internal_import(
+    &self,
+    referrer: &ModuleSpecifier,
+    resolved: &ModuleSpecifier,
+  ) -> Option<&ModuleSpecifier> {
+    if self.exports.contains(resolved) {
+      return None;
+    }
+    let resolved_export = owning_export(&self.exports, resolved)?;
+    match owning_export(&self.exports, referrer) {
+      Some(referrer_export)
+        if export_dir(referrer_export) == export_dir(resolved_export) =>
+      {
+        None
+      }
+      _ => Some(resolved_export),
+    }
+  }
+
+  fn relative_specifier(
+    &self,
+    resolved: &ModuleSpecifier,
+    referrer: &ModuleSpecifier,
+  ) -> Option<String> {
+    let resolved = &self.canonicalize(resolved);
+    let referrer = &self.canonicalize(referrer);
+    #[cfg(test)]
+    if self.relative_style == RelativeStyle::AbsoluteFileUrl
+      && resolved.scheme() == "file"
+    {
+      return Some(resolved.to_string());
+    }
+    #[cfg(test)]
+    if self.relative_style == RelativeStyle::BareFromRoot {
+      if let Some(package_root) = &self.package_root {
+        if resolved.scheme() == "file"
+          && resolved.as_str().starts_with(package_root.as_str())
+        {
+          let relative = package_root.make_relative(resolved)?;
+          return Some(format!("/{}", relative));
+        }
+      }
+    }
+    relative_url(resolved, referrer)
+  }
+
+  /// Gets the real path of a file specifier when `canonicalize_paths` is
+  /// enabled, falling back to the specifier when it doesn't exist.
+  fn canonicalize(&self, specifier: &ModuleSpecifier) -> ModuleSpecifier {
+    self
+      .sloppy_imports_resolver
+      .as_deref()
+      .filter(|_| self.canonicalize_paths && specifier.scheme() == "file")
+      .and_then(|resolver| resolver.canonicalize(specifier))
+      .unwrap_or_else(|| specifier.clone())
+  }
+
+  /// Attempts to unfurl the dynamic dependency returning `true` on success
+  /// or `false` when the import was not analyzable.
+  #[allow(clippy::too_many_arguments)]
+  fn try_unfurl_dynamic_dep(
+    &self,
+    module_url: &lsp_types::Url,
+    parsed_source: &ParsedSource,
+    dep: &deno_graph::DynamicDependencyDescriptor,
+    output_base: Option<&ModuleSpecifier>,
+    text_changes: &mut Vec<deno_ast::TextChange>,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+    resolved_reporter: &mut dyn FnMut(Option<&str>, &ModuleSpecifier),
+  ) -> bool {
+    match &dep.argument {
+      deno_graph::DynamicArgument::String(specifier) => {
+        // this is the range of the first argument only, so any import
+        // options that follow are left untouched
+        let range = to_range(parsed_source, &dep.argument_range);
+        // the argument is a string literal, so the specifier is the text
+        // between its quotes
+        if range.len() < 2 {
+          return true; // always say it's analyzable for a string
+        }
+        let specifier_range = range.start + 1..range.end - 1;
+        let raw_specifier =
+          &parsed_source.text_info().text_str()[specifier_range.clone()];
+        if raw_specifier != specifier {
+          // the literal has escapes, so leave it as-is
+          return true;
+        }
+        let unfurled = self.unfurl_specifier(
+          module_url,
+          specifier,
+          parsed_source.text_info(),
+          to_source_range(parsed_source, &specifier_range),
+          output_base,
+          diagnostic_reporter,
+          resolved_reporter,
+        );
+        if let Some(unfurled) = unfurled {
+          text_changes.push(deno_ast::TextChange {
+            range: specifier_range,
+            new_text: unfurled,
+          });
+        }
+        true
+      }
+      deno_graph::DynamicArgument::Template(parts) => match parts.first() {
+        Some(DynamicTemplatePart::String { value: specifier }) => {
+          // relative doesn't need to be modified
+          let is_relative =
+            specifier.starts_with("./") || specifier.starts_with("../");
+          if is_relative {
+            return true;
+          }
+          if !specifier.ends_with('/') {
+            return false;
+          }
+          let range = to_range(parsed_source, &dep.argument_range);
+          // the value has its escapes processed, so use the raw source text
+          // of the template's head rather than searching for the value
+          let argument_text =
+            &parsed_source.text_info().text_str()[range.clone()];
+          let Some(head_len) = raw_template_head_len(argument_text) else {
+            return false;
+          };
+          let start = range.start + 1;
+          let specifier_range = start..start + head_len;
+          let unfurled = match self.package_alias_prefix(module_url, specifier)
+          {
+            Some(prefix) => Some(prefix),
+            None => self.unfurl_specifier(
+              module_url,
+              specifier,
+              parsed_source.text_info(),
+              to_source_range(parsed_source, &specifier_range),
+              output_base,
+              diagnostic_reporter,
+              resolved_reporter,
+            ),
+          };
+          let Some(unfurled) = unfurled else {
+            return true; // nothing to unfurl
+          };
+          text_changes.push(deno_ast::TextChange {
+            range: specifier_range,
+            new_text: unfurled,
+          });
+          true
+        }
+        Some(DynamicTemplatePart::Expr) => {
+          false // failed analyzing
+        }
+        None => {
+          true // ignore
+        }
+      },
+      deno_graph::DynamicArgument::Expr => {
+        false // failed analyzing
+      }
+    }
+  }
+
+  /// Unfurls the specifiers of the module's dependencies.
+  ///
+  /// With the current version of deno_graph's analyzer this covers static
+  /// imports and exports (including the `import defer` and `import source`
+  /// phases, which are analyzed as regular static imports), dynamic imports
+  /// with a string or template literal argument, `@deno-types` comments,
+  /// triple-slash references, JSDoc imports, and JSX import source pragmas.
+  pub fn unfurl(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> String {
+    self.unfurl_with_options(
+      url,
+      parsed_source,
+      &UnfurlOptions::default(),
+      diagnostic_reporter,
+    )
+  }
+
+  /// Unfurls the module, collecting its diagnostics rather than reporting
+  /// them as they're found.
+  #[cfg(test)]
+  pub fn unfurl_collect(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+  ) -> (String, Vec<SpecifierUnfurlerDiagnostic>) {
+    let mut diagnostics = Vec::new();
+    let text = self.unfurl(url, parsed_source, &mut |diagnostic| {
+      diagnostics.push(diagnostic)
+    });
+    (text, diagnostics)
+  }
+
+  /// Unfurls the module with options that only apply to this module.
+  pub fn unfurl_with_options(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    options: &UnfurlOptions,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> String {
+    let output_base = options.output_base.as_ref().map(|output_base| {
+      let mut output_base = output_base.clone();
+      if !output_base.path().ends_with('/') {
+        let path = format!("{}/", output_base.path());
+        output_base.set_path(&path);
+      }
+      output_base
+    });
+    let kinds = if options.unfurl_type_references {
+      options.kinds
+    } else {
+      options.kinds.difference(DependencyKinds::TS_REFERENCES)
+    };
+    self.unfurl_inner(
+      url,
+      parsed_source,
+      output_base.as_ref(),
+      kinds,
+      &mut |diagnostic| {
+        diagnostic_reporter(diagnostic);
+        ControlFlow::Continue(())
+      },
+      &mut |_, _| {},
+    )
+  }
+
+  /// Unfurls the module unless the cache has the result for the same
+  /// source text, in which case its diagnostics are reported again.
+  #[cfg(test)]
+  pub fn unfurl_cached(
+    &self,
+    cache: &mut UnfurlCache,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> String {
+    let source_text = parsed_source.text_info().text_str();
+    if let Some((text, diagnostics)) = cache.get(url, source_text) {
+      for diagnostic in diagnostics {
+        diagnostic_reporter(diagnostic.clone());
+      }
+      return text.to_string();
+    }
+    let mut diagnostics = Vec::new();
+    let text = self.unfurl(url, parsed_source, &mut |diagnostic| {
+      diagnostics.push(diagnostic)
+    });
+    for diagnostic in &diagnostics {
+      diagnostic_reporter(diagnostic.clone());
+    }
+    cache.insert(url.clone(), source_text, text.clone(), diagnostics);
+    text
+  }
+
+  /// Unfurls the `@import` and `url()` references of a CSS module.
+  ///
+  /// CSS can't be parsed as a module, so this scans the text for references
+  /// instead. `data:` urls and fragments (ex. `url(#gradient)`) are left as-is.
+  pub fn unfurl_css(
+    &self,
+    url: &ModuleSpecifier,
+    text: &str,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> String {
+    let mut diagnostic_reporter = |diagnostic: SpecifierUnfurlerDiagnostic| {
+      if !self.ignored_codes.contains(diagnostic.code()) {
+        diagnostic_reporter(diagnostic);
+      }
+    };
+    let text_info = SourceTextInfo::from_string(text.to_string());
+    let mut text_changes = Vec::new();
+    for range in css_specifier_ranges(text) {
+      let specifier = &text[range.clone()];
+      if specifier.starts_with('#') || specifier.starts_with("data:") {
+        continue;
+      }
+      let source_range = SourceRange::new(
+        text_info.range().start + range.start,
+        text_info.range().start + range.end,
+      );
+      if let Some(unfurled) = self.unfurl_specifier(
+        url,
+        specifier,
+        &text_info,
+        source_range,
+        None,
+        &mut diagnostic_reporter,
+        &mut |_, _| {},
+      ) {
+        text_changes.push(deno_ast::TextChange {
+          range,
+          new_text: unfurled,
+        });
+      }
+    }
+    if self.report_only {
+      return text.to_string();
+    }
+    deno_ast::apply_text_changes(text, text_changes)
+  }
+
+  /// Unfurls each of a package's modules, collecting the diagnostics of
+  /// each one with its unfurled text.
+  #[cfg(test)]
+  pub fn unfurl_package(
+    &self,
+    files: &[(ModuleSpecifier, ParsedSource)],
+  ) -> Vec<FileUnfurlResult> {
+    files
+      .iter()
+      .map(|(specifier, parsed_source)| {
+        let (text, diagnostics) = self.unfurl_collect(specifier, parsed_source);
+        FileUnfurlResult {
+          specifier: specifier.clone(),
+          text,
+          diagnostics,
+        }
+      })
+      .collect()
+  }
+
+  /// Unfurls the module, stopping as soon as the reporter returns
+  /// `ControlFlow::Break` so the rest of the module isn't analyzed.
+  #[cfg(test)]
+  pub fn try_unfurl(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    diagnostic_reporter: &mut dyn FnMut(
+      SpecifierUnfurlerDiagnostic,
+    ) -> ControlFlow<()>,
+  ) -> ControlFlow<(), String> {
+    let mut stopped = false;
+    let text = self.unfurl_inner(
+      url,
+      parsed_source,
+      None,
+      DependencyKinds::ALL,
+      &mut |diagnostic| {
+        let flow = diagnostic_reporter(diagnostic);
+        stopped |= flow.is_break();
+        flow
+      },
+      &mut |_, _| {},
+    );
+    if stopped {
+      ControlFlow::Break(())
+    } else {
+      ControlFlow::Continue(text)
+    }
+  }
+
+  /// Unfurls the module, also returning the remote (`http:` and `https:`)
+  /// specifiers it imports so they can be vendored.
+  #[cfg(test)]
+  pub fn unfurl_collecting_remotes(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> (String, Vec<ModuleSpecifier>) {
+    let mut remotes = Vec::new();
+    let text = self.unfurl_inner(
+      url,
+      parsed_source,
+      None,
+      DependencyKinds::ALL,
+      &mut |diagnostic| {
+        diagnostic_reporter(diagnostic);
+        ControlFlow::Continue(())
+      },
+      &mut |_, resolved| {
+        if matches!(resolved.scheme(), "http" | "https")
+          && !remotes.contains(resolved)
+        {
+          remotes.push(resolved.clone());
+        }
+      },
+    );
+    (text, remotes)
+  }
+
+  /// Unfurls the module, also returning stats about what was rewritten.
+  #[cfg(test)]
+  pub fn unfurl_with_stats(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> (String, UnfurlStats) {
+    let mut stats = UnfurlStats::default();
+    let text = self.unfurl_inner(
+      url,
+      parsed_source,
+      None,
+      DependencyKinds::ALL,
+      &mut |diagnostic| {
+        diagnostic_reporter(diagnostic);
+        ControlFlow::Continue(())
+      },
+      &mut |_, resolved| {
+        if resolved.scheme() == "node" {
+          let name = resolved.path();
+          let name = name.split_once('/').map(|(name, _)| name).unwrap_or(name);
+          stats.node_builtins.insert(name.to_string());
+        }
+      },
+    );
+    (text, stats)
+  }
+
+  /// Unfurls the module, recording the import map entries its specifiers
+  /// were resolved with.
+  #[cfg(test)]
+  pub fn unfurl_tracking_import_map_usage(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+    usage_tracker: &mut ImportMapUsageTracker,
+  ) -> String {
+    self.unfurl_inner(
+      url,
+      parsed_source,
+      None,
+      DependencyKinds::ALL,
+      &mut |diagnostic| {
+        diagnostic_reporter(diagnostic);
+        ControlFlow::Continue(())
+      },
+      &mut |import_map_key, _| {
+        if let Some(key) = import_map_key {
+          usage_tracker.record_used(key);
+        }
+      },
+    )
+  }
+
+  /// Parses and unfurls the source text of a module. The source is
+  /// returned unchanged when it fails to parse.
+  pub fn unfurl_source(
+    &self,
+    url: &ModuleSpecifier,
+    source: &str,
+    media_type: MediaType,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> String {
+    let text_info = SourceTextInfo::from_string(source.to_string());
+    let parsed_source = match deno_ast::parse_module(deno_ast::ParseParams {
+      specifier: url.clone(),
+      text_info: text_info.clone(),
+      media_type,
+      capture_tokens: false,
+      scope_analysis: false,
+      maybe_syntax: None,
+    }) {
+      Ok(parsed_source) => parsed_source,
+      Err(diagnostic) => {
+        let diagnostic = SpecifierUnfurlerDiagnostic::ParseError {
+          specifier: url.clone(),
+          text_info,
+          range: diagnostic.range,
+          message: diagnostic.kind.msg().to_string(),
+        };
+        if !self.ignored_codes.contains(diagnostic.code()) {
+          diagnostic_reporter(diagnostic);
+        }
+        return source.to_string();
+      }
+    };
+    self.unfurl(url, &parsed_source, diagnostic_reporter)
+  }
+
+  fn unfurl_inner(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    output_base: Option<&ModuleSpecifier>,
+    kinds: DependencyKinds,
+    diagnostic_reporter: &mut dyn FnMut(
+      SpecifierUnfurlerDiagnostic,
+    ) -> ControlFlow<()>,
+    resolved_reporter: &mut dyn FnMut(Option<&str>, &ModuleSpecifier),
+  ) -> String {
+    // once the reporter breaks, ignore further diagnostics and stop
+    // analyzing the module
+    let stopped = Cell::new(false);
+    let diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic) =
+      &mut |diagnostic| {
+        if stopped.get() || self.ignored_codes.contains(diagnostic.code()) {
+          return;
+        }
+        if diagnostic_reporter(diagnostic).is_break() {
+          stopped.set(true);
+        }
+      };
+    let mut text_changes = Vec::new();
+    let module_info = DefaultModuleAnalyzer::module_info(parsed_source);
+    let analyze_specifier = |specifier: &str,
+                             range: &deno_graph::PositionRange,
+                             text_changes: &mut Vec<deno_ast::TextChange>,
+                             diagnostic_reporter: &mut dyn FnMut(
+      SpecifierUnfurlerDiagnostic,
+    ),
+                             resolved_reporter: &mut dyn FnMut(
+      Option<&str>,
+      &ModuleSpecifier,
+    )| {
+      let range = to_range(parsed_source, range);
+      if let Some(unfurled) = self.unfurl_specifier(
+        url,
+        specifier,
+        parsed_source.text_info(),
+        to_source_range(parsed_source, &range),
+        output_base,
+        diagnostic_reporter,
+        resolved_reporter,
+      ) {
+        text_changes.push(deno_ast::TextChange {
+          range,
+          new_text: unfurled,
+        });
+      }
+    };
+    for dep in &module_info.dependencies {
+      if stopped.get() {
+        break;
+      }
+      let kind = match dep {
+        DependencyDescriptor::Static(_) => DependencyKinds::STATIC,
+        DependencyDescriptor::Dynamic(_) => DependencyKinds::DYNAMIC,
+      };
+      if !kinds.contains(kind) {
+        continue;
+      }
+      let types_pragma = analyze_deno_types(dep.leading_comments())
+        .map(|pragma| (pragma.specifier, pragma.range))
+        .or_else(|| analyze_ts_types(dep.leading_comments()));
+      if let Some((types_specifier, types_range)) = types_pragma {
+        analyze_specifier(
+          &types_specifier,
+          &deno_types_specifier_range(
+            parsed_source,
+            &types_specifier,
+            &types_range,
+          ),
+          &mut text_changes,
+          diagnostic_reporter,
+          resolved_reporter,
+        );
+      }
+      match dep {
+        DependencyDescriptor::Static(dep) => {
+          analyze_specifier(
+            &dep.specifier,
+            &dep.specifier_range,
+            &mut text_changes,
+            diagnostic_reporter,
+            resolved_reporter,
+          );
+        }
+        DependencyDescriptor::Dynamic(dep) => {
+          self.unfurl_dynamic_dep(
+            url,
+            parsed_source,
+            dep,
+            output_base,
+            &mut text_changes,
+            diagnostic_reporter,
+            resolved_reporter,
+          );
+        }
+      }
+    }
+    for ts_ref in &module_info.ts_references {
+      if stopped.get() || !kinds.contains(DependencyKinds::TS_REFERENCES) {
+        break;
+      }
+      let specifier_with_range = match ts_ref {
+        TypeScriptReference::Path(range) => range,
+        TypeScriptReference::Types(range) => range,
+      };
+      analyze_specifier(
+        &specifier_with_range.text,
+        &specifier_with_range.range,
+        &mut text_changes,
+        diagnostic_reporter,
+        resolved_reporter,
+      );
+    }
+    for specifier_with_range in &module_info.jsdoc_imports {
+      if stopped.get() || !kinds.contains(DependencyKinds::JSDOC) {
+        break;
+      }
+      analyze_specifier(
+        &specifier_with_range.text,
+        &specifier_with_range.range,
+        &mut text_changes,
+        diagnostic_reporter,
+        resolved_reporter,
+      );
+    }
+    // deno_graph only analyzes `{import("...")}` types in JSDoc comments
+    for (specifier, range) in jsdoc_import_tags(parsed_source) {
+      if stopped.get() || !kinds.contains(DependencyKinds::JSDOC) {
+        break;
+      }
+      if let Some(unfurled) = self.unfurl_specifier(
+        url,
+        &specifier,
+        parsed_source.text_info(),
+        to_source_range(parsed_source, &range),
+        output_base,
+        diagnostic_reporter,
+        resolved_reporter,
+      ) {
+        text_changes.push(deno_ast::TextChange {
+          range,
+          new_text: unfurled,
+        });
+      }
+    }
+    for (specifier, range) in import_meta_url_specifiers(parsed_source) {
+      if stopped.get() || !kinds.contains(DependencyKinds::IMPORT_META_URL) {
+        break;
+      }
+      if let Some(unfurled) = self.unfurl_specifier(
+        url,
+        &specifier,
+        parsed_source.text_info(),
+        to_source_range(parsed_source, &range),
+        output_base,
+        diagnostic_reporter,
+        resolved_reporter,
+      ) {
+        text_changes.push(deno_ast::TextChange {
+          range,
+          new_text: unfurled,
+        });
+      }
+    }
+    // this is the only JSX pragma with a specifier, since `@jsx` and
+    // `@jsxFrag` name factory functions and `@jsxRuntime` is either
+    // `classic` or `automatic`
+    if let Some(specifier_with_range) = module_info
+      .jsx_import_source
+      .as_ref()
+      .filter(|_| kinds.contains(DependencyKinds::JSX_IMPORT_SOURCE))
+    {
+      analyze_specifier(
+        &specifier_with_range.text,
+        &specifier_with_range.range,
+        &mut text_changes,
+        diagnostic_reporter,
+        resolved_reporter,
+      );
+    }
+    if stopped.get() || self.report_only {
+      return parsed_source.text_info().text_str().to_string();
+    }
+
+    self.apply_text_changes(
+      url,
+      parsed_source,
+      text_changes,
+      diagnostic_reporter,
+    )
+  }
+
+  /// Unfurls a dynamic import, reporting it when its argument can't be
+  /// analyzed. Returns whether it could be analyzed.
+  #[allow(clippy::too_many_arguments)]
+  fn unfurl_dynamic_dep(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    dep: &deno_graph::DynamicDependencyDescriptor,
+    output_base: Option<&ModuleSpecifier>,
+    text_changes: &mut Vec<deno_ast::TextChange>,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+    resolved_reporter: &mut dyn FnMut(Option<&str>, &ModuleSpecifier),
+  ) -> bool {
+    let success = self.try_unfurl_dynamic_dep(
+      url,
+      parsed_source,
+      dep,
+      output_base,
+      text_changes,
+      diagnostic_reporter,
+      resolved_reporter,
+    );
+
+    if !success {
+      let range =
+        to_clamped_source_range(parsed_source.text_info(), &dep.argument_range);
+      diagnostic_reporter(
+        SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport {
+          specifier: url.to_owned(),
+          range,
+          text_info: parsed_source.text_info().clone(),
+          argument_text: parsed_source
+            .text_info()
+            .range_text(&range)
+            .to_string(),
+        },
+      );
+    }
+    success
+  }
+
+  /// Counts the module's dynamic imports that can and can't be analyzed,
+  /// without unfurling anything.
+  #[cfg(test)]
+  pub fn analyze_dynamic_imports(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+  ) -> (usize, usize) {
+    let module_info = DefaultModuleAnalyzer::module_info(parsed_source);
+    let mut analyzable = 0;
+    let mut unanalyzable = 0;
+    for dep in &module_info.dependencies {
+      if let DependencyDescriptor::Dynamic(dep) = dep {
+        let success = self.unfurl_dynamic_dep(
+          url,
+          parsed_source,
+          dep,
+          None,
+          &mut Vec::new(),
+          &mut |_| {},
+          &mut |_, _| {},
+        );
+        if success {
+          analyzable += 1;
+        } else {
+          unanalyzable += 1;
+        }
+      }
+    }
+    (analyzable, unanalyzable)
+  }
+
+  fn apply_text_changes(
+    &self,
+    url: &ModuleSpecifier,
+    parsed_source: &ParsedSource,
+    text_changes: Vec<deno_ast::TextChange>,
+    diagnostic_reporter: &mut dyn FnMut(SpecifierUnfurlerDiagnostic),
+  ) -> String {
+    let original_text = parsed_source.text_info().text_str();
+    let text_changes = match self.normalize_quotes {
+      Some(quote) => normalize_quotes(original_text, text_changes, quote),
+      None => text_changes,
+    };
+    let rewritten_text =
+      deno_ast::apply_text_changes(original_text, text_changes);
+    if self.validate {
+      let parse_result = deno_ast::parse_module(deno_ast::ParseParams {
+        specifier: url.clone(),
+        text_info: SourceTextInfo::from_string(rewritten_text.clone()),
+        media_type: parsed_source.media_type(),
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+      });
+      if let Err(diagnostic) = parse_result {
+        diagnostic_reporter(
+          SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource {
+            specifier: url.clone(),
+            message: diagnostic.kind.msg().to_string(),
+          },
+        );
+        return original_text.to_string();
+      }
+    }
+    rewritten_text
+  }
+}
+
+/// Expands the text changes of quoted specifiers to include their quotes,
+/// rewriting the quotes to `quote`. Changes that aren't the whole contents of
+/// a string (ex. the head of a template literal) are left as-is.
+fn normalize_quotes(
+  text: &str,
+  text_changes: Vec<deno_ast::TextChange>,
+  quote: char,
+) -> Vec<deno_ast::TextChange> {
+  text_changes
+    .into_iter()
+    .map(|change| {
+      let before = text[..change.range.start].chars().next_back();
+      let after = text[change.range.end..].chars().next();
+      match (before, after) {
+        (Some(before), Some(after))
+          if before == after
+            && matches!(before, '"' | '\'')
+            && before != quote
+            && !change.new_text.contains([quote, '\\']) =>
+        {
+          deno_ast::TextChange {
+            range: change.range.start - 1..change.range.end + 1,
+            new_text: format!("{quote}{}{quote}", change.new_text),
+          }
+        }
+        _ => change,
+      }
+    })
+    .collect()
+}
+
+/// Gets the specifier to use for `resolved` from within `referrer`.
+///
+/// Returns `None` when a file specifier can't be made relative to the
+/// referrer (ex. different drive letters on Windows). Remote specifiers are
+/// made relative when they're on the same origin as the referrer and are
+/// absolute otherwise.
+///
+/// A referrer with a trailing slash (ex. `file:///project/`) is treated as a
+/// directory, so specifiers are made relative to it rather than its parent.
+fn relative_url(
+  resolved: &ModuleSpecifier,
+  referrer: &ModuleSpecifier,
+) -> Option<String> {
+  match resolved.scheme() {
+    "file" => {
+      // `make_relative` already resolves against the last `/` of the
+      // referrer, which is the directory itself for a directory referrer
+      let relative = referrer.make_relative(resolved)?;
+      Some(format!("./{}", relative))
+    }
+    "http" | "https" => match referrer.make_relative(resolved) {
+      Some(relative) if relative.starts_with("../") => Some(relative),
+      Some(relative) if !relative.is_empty() => Some(format!("./{}", relative)),
+      _ => Some(resolved.to_string()),
+    },
+    _ => Some(resolved.to_string()),
+  }
+}
+
+/// Gets if the file specifier is named like a test module (ex. `foo_test.ts`
+/// or `foo.test.ts`).
+fn is_test_module(specifier: &ModuleSpecifier) -> bool {
+  specifier_to_file_path(specifier)
+    .map(|path| is_supported_test_path(&path))
+    .unwrap_or(false)
+}
+
+/// Gets the directory of an export's entrypoint, with a trailing slash.
+fn export_dir(export: &ModuleSpecifier) -> &str {
+  let url = export.as_str();
+  &url[..url.rfind('/').map(|index| index + 1).unwrap_or(url.len())]
+}
+
+/// Gets the export with the most specific directory the module is in.
+fn owning_export<'a>(
+  exports: &'a [ModuleSpecifier],
+  specifier: &ModuleSpecifier,
+) -> Option<&'a ModuleSpecifier> {
+  exports
+    .iter()
+    .filter(|export| specifier.as_str().starts_with(export_dir(export)))
+    .max_by_key(|export| export_dir(export).len())
+}
+
+/// Gets the key of the import map entry the specifier was resolved to
+/// `resolved` with. The entry's value has to lead to `resolved`, so an
+/// entry that matches without taking precedence isn't picked.
+fn resolved_import_map_key<'a>(
+  import_map: &'a ImportMap,
+  specifier: &str,
+  referrer: &ModuleSpecifier,
+  resolved: &ModuleSpecifier,
+) -> Option<&'a str> {
+  let normalized_specifier =
+    normalize_import_map_specifier(specifier, referrer)?;
+  import_map
+    .entries_for_referrer(referrer)
+    .find(|entry| match entry.value {
+      Some(value) if entry.key == normalized_specifier => value == resolved,
+      Some(value) => {
+        entry.key.ends_with('/')
+          && normalized_specifier.starts_with(entry.key)
+          && resolved.as_str().starts_with(value.as_str())
+      }
+      None => false,
+    })
+    .map(|entry| entry.raw_key)
+}
+
+/// Gets the import map entry the specifier is resolved with.
+fn matched_import_map_entry<'a>(
+  import_map: &'a ImportMap,
+  specifier: &str,
+  referrer: &ModuleSpecifier,
+) -> Option<import_map::SpecifierMapEntry<'a>> {
+  let normalized_specifier =
+    normalize_import_map_specifier(specifier, referrer)?;
+  // exact matches take precedence over package prefix matches
+  if let Some(entry) = import_map
+    .entries_for_referrer(referrer)
+    .find(|entry| entry.key == normalized_specifier)
+  {
+    return Some(entry);
+  }
+  import_map.entries_for_referrer(referrer).find(|entry| {
+    entry.key.ends_with('/') && normalized_specifier.starts_with(entry.key)
+  })
+}
+
+/// Gets the keys of all the import map entries that match the specifier,
+/// including ones that don't take precedence.
+fn matching_import_map_keys<'a>(
+  import_map: &'a ImportMap,
+  specifier: &str,
+  referrer: &ModuleSpecifier,
+) -> Vec<&'a str> {
+  let Some(normalized_specifier) =
+    normalize_import_map_specifier(specifier, referrer)
+  else {
+    return Vec::new();
+  };
+  let mut keys = Vec::new();
+  for entry in import_map.entries_for_referrer(referrer) {
+    let is_match = entry.key == normalized_specifier
+      || (entry.key.ends_with('/')
+        && normalized_specifier.starts_with(entry.key));
+    // a scope can have the same key as the top level imports
+    if is_match && !keys.contains(&entry.raw_key) {
+      keys.push(entry.raw_key);
+    }
+  }
+  keys
+}
+
+/// Gets the import map key a bare specifier would match if it had a trailing
+/// slash (ex. `lib/` for `lib`).
+fn trailing_slash_import_map_key<'a>(
+  import_map: &'a ImportMap,
+  specifier: &str,
+  referrer: &ModuleSpecifier,
+) -> Option<&'a str> {
+  if specifier.is_empty()
+    || specifier.starts_with('.')
+    || specifier.starts_with('/')
+    || specifier.ends_with('/')
+    || ModuleSpecifier::parse(specifier).is_ok()
+  {
+    return None;
+  }
+  let key = format!("{specifier}/");
+  import_map
+    .entries_for_referrer(referrer)
+    .find(|entry| entry.key == key)
+    .map(|entry| entry.raw_key)
+}
+
+fn normalize_import_map_specifier(
+  specifier: &str,
+  referrer: &ModuleSpecifier,
+) -> Option<String> {
+  if specifier.starts_with("./")
+    || specifier.starts_with("../")
+    || specifier.starts_with('/')
+  {
+    Some(referrer.join(specifier).ok()?.to_string())
+  } else if let Ok(url) = ModuleSpecifier::parse(specifier) {
+    Some(url.to_string())
+  } else {
+    Some(specifier.to_string())
+  }
+}
+
+/// Gets the number of leading `../` segments of a relative specifier.
+fn relative_depth(specifier: &str) -> usize {
+  let mut rest = specifier.strip_prefix("./").unwrap_or(specifier);
+  let mut depth = 0;
+  while let Some(next) = rest.strip_prefix("../") {
+    rest = next;
+    depth += 1;
+  }
+  depth
+}
+
+/// Searches the comments before a dependency for a `@ts-types` pragma,
+/// which deno_graph doesn't analyze yet. The range is in the same form as
+/// `analyze_deno_types` gives for a `@deno-types` pragma.
+fn analyze_ts_types(
+  leading_comments: &[deno_graph::Comment],
+) -> Option<(String, deno_graph::PositionRange)> {
+  let comment = leading_comments.last()?;
+  let captures = TS_TYPES_RE.captures(&comment.text)?;
+  let m = captures.get(1).or_else(|| captures.get(2))?;
+  // the comment text starts after the double slash or slash star and the
+  // range includes the quotes
+  let start = &comment.range.start;
+  let character = start.character + 2;
+  Some((
+    m.as_str().to_string(),
+    deno_graph::PositionRange {
+      start: deno_graph::Position {
+        line: start.line,
+        character: character + m.start() - 1,
+      },
+      end: deno_graph::Position {
+        line: start.line,
+        character: character + m.end() + 1,
+      },
+    },
+  ))
+}
+
+/// Gets the range of a `@deno-types` pragma's specifier, including its quotes
+/// when it has them.
+///
+/// The range deno_graph reports always assumes quotes, which means it extends
+/// past the specifier (and possibly the end of the line) for unquoted ones,
+/// so this is computed from the captured specifier instead.
+fn deno_types_specifier_range(
+  parsed_source: &ParsedSource,
+  specifier: &str,
+  range: &deno_graph::PositionRange,
+) -> deno_graph::PositionRange {
+  let start = &range.start;
+  let line_text = parsed_source.text_info().line_text(start.line);
+  let is_quoted = matches!(
+    line_text.chars().nth(start.character),
+    Some('"') | Some('\'')
+  );
+  let specifier_start = start.character + 1;
+  let specifier_end = specifier_start + specifier.chars().count();
+  let (start_character, end_character) = if is_quoted {
+    (start.character, specifier_end + 1)
+  } else {
+    (specifier_start, specifier_end)
+  };
+  deno_graph::PositionRange {
+    start: deno_graph::Position {
+      line: start.line,
+      character: start_character,
+    },
+    end: deno_graph::Position {
+      line: start.line,
+      character: end_character,
+    },
+  }
+}
+
+/// Gets the byte length of the raw text before the first substitution of a
+/// template literal (ex. `lib/` in `` `lib/${name}` ``).
+fn raw_template_head_len(template_text: &str) -> Option<usize> {
+  let head = template_text.strip_prefix('`')?;
+  let mut chars = head.char_indices().peekable();
+  while let Some((index, c)) = chars.next() {
+    match c {
+      '\\' => {
+        chars.next(); // skip the escaped character
+      }
+      '$' if matches!(chars.peek(), Some((_, '{'))) => return Some(index),
+      '`' => return Some(index),
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Finds the specifiers of the JSDoc `@import` tags in a JavaScript module
+/// along with their byte ranges.
+fn jsdoc_import_tags(
+  parsed_source: &ParsedSource,
+) -> Vec<(String, std::ops::Range<usize>)> {
+  // like deno_graph, only analyze these in JavaScript modules
+  if !matches!(
+    parsed_source.media_type(),
+    MediaType::JavaScript | MediaType::Jsx | MediaType::Mjs | MediaType::Cjs
+  ) {
+    return Vec::new();
+  }
+  let text_start = parsed_source.text_info().range().start;
+  let mut tags = Vec::new();
+  for comment in parsed_source.comments().get_vec() {
+    if comment.kind != CommentKind::Block || !comment.text.starts_with('*') {
+      continue;
+    }
+    // the comment's text starts after the opening `/*`
+    let text_offset = comment.range().as_byte_range(text_start).start + 2;
+    for captures in JSDOC_IMPORT_TAG_RE.captures_iter(&comment.text) {
+      if let Some(m) = captures.get(1) {
+        tags.push((
+          m.as_str().to_string(),
+          text_offset + m.start()..text_offset + m.end(),
+        ));
+      }
+    }
+  }
+  tags
+}
+
+/// Finds the specifiers of `new URL("...", import.meta.url)` expressions,
+/// which reference files relative to the module (ex. assets or workers),
+/// along with their byte ranges.
+fn import_meta_url_specifiers(
+  parsed_source: &ParsedSource,
+) -> Vec<(String, std::ops::Range<usize>)> {
+  let mut collector = ImportMetaUrlCollector {
+    specifiers: Vec::new(),
+  };
+  parsed_source.program_ref().visit_with(&mut collector);
+  let text_start = parsed_source.text_info().range().start;
+  let text_str = parsed_source.text_info().text_str();
+  collector
+    .specifiers
+    .into_iter()
+    .filter_map(|(specifier, range)| {
+      let range = range.as_byte_range(text_start);
+      // exclude the quotes of the string literal
+      let range = range.start + 1..range.end - 1;
+      // a literal with escapes can't be rewritten in place
+      (text_str[range.clone()] == *specifier).then_some((specifier, range))
+    })
+    .collect()
+}
+
+struct ImportMetaUrlCollector {
+  specifiers: Vec<(String, SourceRange)>,
+}
+
+impl Visit for ImportMetaUrlCollector {
+  noop_visit_type!();
+
+  fn visit_new_expr(&mut self, new_expr: &swc_ast::NewExpr) {
+    new_expr.visit_children_with(self);
+    let swc_ast::Expr::Ident(callee) = &*new_expr.callee else {
+      return;
+    };
+    if &*callee.sym != "URL" {
+      return;
+    }
+    let Some([specifier_arg, base_arg]) = new_expr.args.as_deref() else {
+      return;
+    };
+    if specifier_arg.spread.is_some() || base_arg.spread.is_some() {
+      return;
+    }
+    let swc_ast::Expr::Lit(swc_ast::Lit::Str(specifier)) = &*specifier_arg.expr
+    else {
+      return; // not analyzable
+    };
+    let swc_ast::Expr::Member(base) = &*base_arg.expr else {
+      return;
+    };
+    let is_import_meta_url = matches!(
+      &*base.obj,
+      swc_ast::Expr::MetaProp(meta_prop)
+        if meta_prop.kind == swc_ast::MetaPropKind::ImportMeta
+    ) && matches!(
+      &base.prop,
+      swc_ast::MemberProp::Ident(prop) if &*prop.sym == "url"
+    );
+    if is_import_meta_url {
+      self
+        .specifiers
+        .push((specifier.value.to_string(), specifier.range()));
+    }
+  }
+}
+
+/// Converts a byte range within the source text into a `SourceRange`.
+fn to_source_range(
+  parsed_source: &ParsedSource,
+  range: &std::ops::Range<usize>,
+) -> SourceRange {
+  let start = parsed_source.text_info().range().start;
+  SourceRange::new(start + range.start, start + range.end)
+}
+
+/// Gets the byte ranges of the specifiers referenced by a CSS module,
+/// excluding any quotes and ignoring references in comments.
+fn css_specifier_ranges(text: &str) -> Vec<std::ops::Range<usize>> {
+  // blank out comments so their references aren't matched, keeping the byte
+  // offsets the same
+  let mut bytes = text.as_bytes().to_vec();
+  let mut index = 0;
+  while let Some(start) = text[index..].find("/*").map(|i| i + index) {
+    let end = text[start + 2..]
+      .find("*/")
+      .map(|i| i + start + 4)
+      .unwrap_or(text.len());
+    bytes[start..end].fill(b' ');
+    index = end;
+  }
+  // only ascii bytes were replaced, so this is still valid utf-8
+  let masked = String::from_utf8(bytes).unwrap();
+  let mut ranges = CSS_IMPORT_RE
+    .captures_iter(&masked)
+    .chain(CSS_URL_RE.captures_iter(&masked))
+    .filter_map(|captures| {
+      captures
+        .iter()
+        .skip(1)
+        .flatten()
+        .next()
+        .filter(|m| !m.as_str().trim().is_empty())
+        .map(|m| m.range())
+    })
+    .collect::<Vec<_>>();
+  ranges.sort_by_key(|range| range.start);
+  ranges
+}
+
+/// Converts a position from deno_graph to a source position, clamping it to
+/// the source text so that an out of bounds position can't cause a panic.
+fn to_clamped_source_pos(
+  text_info: &SourceTextInfo,
+  position: &deno_graph::Position,
+) -> SourcePos {
+  if position.line >= text_info.lines_count() {
+    return text_info.range().end;
+  }
+  // this clips the character to the end of the line
+  position.as_source_pos(text_info)
+}
+
+fn to_clamped_source_range(
+  text_info: &SourceTextInfo,
+  range: &deno_graph::PositionRange,
+) -> SourceRange {
+  let start = to_clamped_source_pos(text_info, &range.start);
+  let end = to_clamped_source_pos(text_info, &range.end);
+  SourceRange::new(start, end.max(start))
+}
+
+fn to_range(
+  parsed_source: &ParsedSource,
+  range: &deno_graph::PositionRange,
+) -> std::ops::Range<usize> {
+  let mut range = to_clamped_source_range(parsed_source.text_info(), range)
+    .as_byte_range(parsed_source.text_info().range().start);
+  let text = &parsed_source.text_info().text_str()[range.clone()];
+  // only trim a matching pair of quotes, since the other kind of quote may
+  // be part of the specifier (ex. `"lib/o'brien.ts"`)
+  let is_quoted = text.len() >= 2
+    && ((text.starts_with('"') && text.ends_with('"'))
+      || (text.starts_with('\'') && text.ends_with('\'')));
+  if is_quoted {
+    range.start += 1;
+    range.end -= 1;
+  }
+  let text_str = parsed_source.text_info().text_str();
+  debug_assert!(text_str.is_char_boundary(range.start));
+  debug_assert!(text_str.is_char_boundary(range.end));
+  range
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+  use std::path::Path;
+  use std::path::PathBuf;
+  use std::rc::Rc;
+  use std::sync::Arc;
+
+  use crate::args::package_json::get_local_package_json_version_reqs;
+  use crate::args::PackageJsonDepsProvider;
+
+  use super::*;
+  use deno_ast::MediaType;
+  use deno_ast::ModuleSpecifier;
+  use deno_core::serde_json::json;
+  use deno_core::url::Url;
+  use deno_runtime::deno_fs::FileSystem;
+  use deno_runtime::deno_fs::FsDirEntry;
+  use deno_runtime::deno_fs::FsFileType;
+  use deno_runtime::deno_fs::OpenOptions;
+  use deno_runtime::deno_fs::RealFs;
+  use deno_runtime::deno_io::fs::File;
+  use deno_runtime::deno_io::fs::FsError;
+  use deno_runtime::deno_io::fs::FsResult;
+  use deno_runtime::deno_io::fs::FsStat;
+  use deno_runtime::deno_node::PackageJson;
+  use import_map::ImportMapWithDiagnostics;
+  use indexmap::IndexMap;
+  use pretty_assertions::assert_eq;
+  use test_util::testdata_path;
+
+  fn parse_ast(specifier: &Url, source_code: &str) -> ParsedSource {
+    let media_type = MediaType::from_specifier(specifier);
+    deno_ast::parse_module(deno_ast::ParseParams {
+      specifier: specifier.clone(),
+      media_type,
+      capture_tokens: false,
+      maybe_syntax: None,
+      scope_analysis: false,
+      text_info: deno_ast::SourceTextInfo::new(source_code.into()),
+    })
+    .unwrap()
+  }
+
+  fn create_mapped_resolver(cwd: &Path) -> MappedSpecifierResolver {
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "express": "npm:express@5",
+        "lib/": "./lib/",
+        "fizz": "./fizz/mod.ts",
+        "@std/fs": "npm:@jsr/std__fs@1",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mut package_json = PackageJson::empty(cwd.join("package.json"));
+    package_json.dependencies =
+      Some(IndexMap::from([("chalk".to_string(), "5".to_string())]));
+    MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(Some(
+        get_local_package_json_version_reqs(&package_json),
+      ))),
+    )
+  }
+
+  fn unfurl_text(
+    unfurler: &SpecifierUnfurler,
+    specifier: &ModuleSpecifier,
+    source_code: &str,
+  ) -> (String, Vec<SpecifierUnfurlerDiagnostic>) {
+    let source = parse_ast(specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let mut reporter = |diagnostic| diagnostics.push(diagnostic);
+    let unfurled_source = unfurler.unfurl(specifier, &source, &mut reporter);
+    (unfurled_source, diagnostics)
+  }
+
+  #[test]
+  fn test_unfurling() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "express": "npm:express@5",
+        "lib/": "./lib/",
+        "fizz": "./fizz/mod.ts",
+        "@std/fs": "npm:@jsr/std__fs@1",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mut package_json = PackageJson::empty(cwd.join("package.json"));
+    package_json.dependencies =
+      Some(IndexMap::from([("chalk".to_string(), "5".to_string())]));
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(Some(
+        get_local_package_json_version_reqs(&package_json),
+      ))),
+    );
+
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+
+    let unfurler = SpecifierUnfurler::new(
+      &mapped_resolver,
+      Some(&sloppy_imports_resolver),
+      true,
+    );
+
+    // Unfurling TS file should apply changes.
+    {
+      let source_code = r#"import express from "express";"
+import foo from "lib/foo.ts";
+import bar from "lib/bar.ts";
+import fizz from "fizz";
+import chalk from "chalk";
+import baz from "./baz";
+import b from "./b.js";
+import b2 from "./b";
+import url from "url";
+// TODO: unfurl these to jsr
+// import "npm:@jsr/std__fs@1/file";
+// import "npm:@jsr/std__fs@1";
+// import "npm:@jsr/std__fs";
+// import "@std/fs";
+
+const test1 = await import("lib/foo.ts");
+const test2 = await import(`lib/foo.ts`);
+const test3 = await import(`lib/${expr}`);
+const test4 = await import(`./lib/${expr}`);
+const test5 = await import("./lib/something.ts");
+const test6 = await import(`./lib/something.ts`);
+// will warn
+const warn1 = await import(`lib${expr}`);
+const warn2 = await import(`${expr}`);
+"#;
+      let specifier =
+        ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+      let source = parse_ast(&specifier, source_code);
+      let mut d = Vec::new();
+      let mut reporter = |diagnostic| d.push(diagnostic);
+      let unfurled_source = unfurler.unfurl(&specifier, &source, &mut reporter);
+      assert_eq!(d.len(), 2);
+      assert!(
+        matches!(
+          d[0],
+          SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
+        ),
+        "{:?}",
+        d[0]
+      );
+      assert!(
+        matches!(
+          d[1],
+          SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
+        ),
+        "{:?}",
+        d[1]
+      );
+      let expected_source = r#"import express from "npm:express@5";"
+import foo from "./lib/foo.ts";
+import bar from "./lib/bar.ts";
+import fizz from "./fizz/mod.ts";
+import chalk from "npm:chalk@5";
+import baz from "./baz/index.js";
+import b from "./b.ts";
+import b2 from "./b.ts";
+import url from "node:url";
+// TODO: unfurl these to jsr
+// import "npm:@jsr/std__fs@1/file";
+// import "npm:@jsr/std__fs@1";
+// import "npm:@jsr/std__fs";
+// import "@std/fs";
+
+const test1 = await import("./lib/foo.ts");
+const test2 = await import(`./lib/foo.ts`);
+const test3 = await import(`./lib/${expr}`);
+const test4 = await import(`./lib/${expr}`);
+const test5 = await import("./lib/something.ts");
+const test6 = await import(`./lib/something.ts`);
+// will warn
+const warn1 = await import(`lib${expr}`);
+const warn2 = await import(`${expr}`);
+"#;
+      assert_eq!(unfurled_source, expected_source);
+    }
+  }
+
+  #[test]
+  fn test_unfurling_node_prefixed_specifiers() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, true);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import url from "node:url";
+import fs from "node:fs/promises";
+import bogus from "node:not-a-builtin";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, source_code);
+  }
+
+  #[test]
+  fn test_unfurling_bare_node_builtin_without_flag() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import fs from "fs";
+const fs2 = await import("fs");
+"#;
+
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in &diagnostics {
+      match diagnostic {
+        SpecifierUnfurlerDiagnostic::BareNodeBuiltin {
+          text_info,
+          range,
+          ..
+        } => {
+          assert_eq!(text_info.range_text(range), "fs");
+        }
+        diagnostic => panic!("unexpected diagnostic: {:?}", diagnostic),
+      }
+    }
+    // the dynamic import's diagnostic covers only the string's contents
+    let SpecifierUnfurlerDiagnostic::BareNodeBuiltin {
+      text_info, range, ..
+    } = &diagnostics[1]
+    else {
+      unreachable!();
+    };
+    let dynamic_start = source_code.find("import(\"fs\")").unwrap() + 8;
+    assert_eq!(
+      range.as_byte_range(text_info.range().start),
+      dynamic_start..dynamic_start + 2
+    );
+    assert_eq!(unfurled_source, source_code);
+
+    // rewritten when the flag is enabled, so no diagnostic
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, true);
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import fs from "node:fs";
+const fs2 = await import("node:fs");
+"#
+    );
+  }
+
+  #[test]
+  fn test_unfurling_deno_types() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, true);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"// @deno-types="lib/foo.d.ts"
+import foo from "lib/foo.js";
+// @deno-types=lib/bar.d.ts
+export * from "lib/bar.js";
+/* @deno-types="lib/baz.d.ts" */
+import baz from "lib/baz.js";
+/* @deno-types=lib/qux.d.ts */
+import qux from "lib/qux.js";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"// @deno-types="./lib/foo.d.ts"
+import foo from "./lib/foo.js";
+// @deno-types=./lib/bar.d.ts
+export * from "./lib/bar.js";
+/* @deno-types="./lib/baz.d.ts" */
+import baz from "./lib/baz.js";
+/* @deno-types=./lib/qux.d.ts */
+import qux from "./lib/qux.js";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_relative_url_unrelatable() {
+    let referrer = ModuleSpecifier::parse("file:///project/mod.ts").unwrap();
+    let resolved =
+      ModuleSpecifier::parse("file://server/share/lib/foo.ts").unwrap();
+    assert_eq!(relative_url(&resolved, &referrer), None);
+
+    let resolved =
+      ModuleSpecifier::parse("file:///project/lib/foo.ts").unwrap();
+    assert_eq!(
+      relative_url(&resolved, &referrer),
+      Some("./lib/foo.ts".to_string())
+    );
+  }
+
+  #[test]
+  fn test_relative_url_remote() {
+    let referrer =
+      ModuleSpecifier::parse("https://deno.land/x/pkg/mod.ts").unwrap();
+    let cases = [
+      ("https://deno.land/x/pkg/util.ts", "./util.ts"),
+      ("https://deno.land/x/pkg/sub/a.ts", "./sub/a.ts"),
+      ("https://deno.land/std/fs/mod.ts", "../../std/fs/mod.ts"),
+      // different hosts, schemes, and ports stay absolute
+      ("https://esm.sh/preact", "https://esm.sh/preact"),
+      (
+        "http://deno.land/x/pkg/util.ts",
+        "http://deno.land/x/pkg/util.ts",
+      ),
+      (
+        "https://deno.land:8080/x/pkg/util.ts",
+        "https://deno.land:8080/x/pkg/util.ts",
+      ),
+    ];
+    for (resolved, expected) in cases {
+      let resolved = ModuleSpecifier::parse(resolved).unwrap();
+      assert_eq!(
+        relative_url(&resolved, &referrer).as_deref(),
+        Some(expected),
+        "{}",
+        resolved
+      );
+    }
+  }
+
+  #[test]
+  fn test_unfurling_remote_referrer() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::parse("https://deno.land/x/pkg/mod.ts").unwrap();
+    let source_code = r#"import a from "./util.ts";
+import b from "https://deno.land/x/pkg/sub/b.ts";
+import c from "https://esm.sh/preact";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import a from "./util.ts";
+import b from "./sub/b.ts";
+import c from "https://esm.sh/preact";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_unrelatable_specifier() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "unc": "file://server/share/lib/foo.ts",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, true);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "unc";
+const bar = await import("unc");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, source_code);
+  }
+
+  #[test]
+  fn test_unfurling_disallowed_schemes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    // local files are only allowed because "file" is in the allowlist
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .bare_node_builtins(true)
+      .allowed_schemes(Some(HashSet::from([
+        "file".to_string(),
+        "jsr".to_string(),
+        "npm".to_string(),
+      ])))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import express from "express";
+import foo from "lib/foo.ts";
+import { join } from "https://deno.land/std@0.200.0/path/mod.ts";
+import { assert } from "jsr:@std/assert@1";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::DisallowedScheme { resolved, .. } => {
+        assert_eq!(
+          resolved.as_str(),
+          "https://deno.land/std@0.200.0/path/mod.ts"
+        );
+      }
+      diagnostic => panic!("unexpected diagnostic: {:?}", diagnostic),
+    }
+    let expected_source = r#"import express from "npm:express@5";
+import foo from "./lib/foo.ts";
+import { join } from "https://deno.land/std@0.200.0/path/mod.ts";
+import { assert } from "jsr:@std/assert@1";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_multi_byte_surroundings() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, true);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"/* 🦕 */ import foo from "lib/foo.ts"; // 日本語
+const 猫 = "🐈"; export { bar } from 'lib/bar.ts';/*ü*/
+const test = await import("lib/foo.ts"); // 🦕🦕
+const test2 = await import(`lib/${猫}`);
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"/* 🦕 */ import foo from "./lib/foo.ts"; // 日本語
+const 猫 = "🐈"; export { bar } from './lib/bar.ts';/*ü*/
+const test = await import("./lib/foo.ts"); // 🦕🦕
+const test2 = await import(`./lib/${猫}`);
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurler_builder_parity() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import express from "express";
+import foo from "lib/foo.ts";
+import chalk from "chalk";
+import baz from "./baz";
+import b2 from "./b";
+import url from "url";
+const test1 = await import("lib/foo.ts");
+const test3 = await import(`lib/${expr}`);
+const warn1 = await import(`lib${expr}`);
+"#;
+
+    let unfurler = SpecifierUnfurler::new(
+      &mapped_resolver,
+      Some(&sloppy_imports_resolver),
+      true,
+    );
+    let (expected_source, expected_diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .bare_node_builtins(true)
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+
+    assert_eq!(unfurled_source, expected_source);
+    assert_eq!(diagnostics.len(), expected_diagnostics.len());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      unfurled_source,
+      r#"import express from "npm:express@5";
+import foo from "./lib/foo.ts";
+import chalk from "npm:chalk@5";
+import baz from "./baz/index.js";
+import b2 from "./b.ts";
+import url from "node:url";
+const test1 = await import("./lib/foo.ts");
+const test3 = await import(`./lib/${expr}`);
+const warn1 = await import(`lib${expr}`);
+"#
+    );
+  }
+
+  #[test]
+  fn test_unfurling_workspace_member() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "@myorg/utils": "../utils/mod.ts",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let member_specifier = ModuleSpecifier::from_file_path(
+      cwd.parent().unwrap().join("utils/mod.ts"),
+    )
+    .unwrap();
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .workspace_members(HashMap::from([(
+        member_specifier,
+        JsrPackageReqReference::from_str("jsr:@myorg/utils@^1").unwrap(),
+      )]))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { a } from "@myorg/utils";
+import { b } from "../utils/mod.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import { a } from "jsr:@myorg/utils@^1";
+import { b } from "jsr:@myorg/utils@^1";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_without_resolving_sloppy_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .resolve_sloppy_imports(false)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "./b";
+import baz from "./baz";
+import foo from "lib/foo";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import b from "./b";
+import baz from "./baz";
+import foo from "./lib/foo";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_without_resolving_sloppy_imports_keeps_casing() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .resolve_sloppy_imports(false)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "./B.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, source_code);
+  }
+
+  #[test]
+  fn test_unanalyzable_dynamic_import_argument_text() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, true);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const warn1 = await import(`lib${expr}`);
+const warn2 = await import("asd " + asd);
+"#;
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    let argument_texts = diagnostics
+      .iter()
+      .map(|diagnostic| match diagnostic {
+        SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport {
+          argument_text,
+          ..
+        } => argument_text.as_str(),
+        diagnostic => panic!("unexpected diagnostic: {:?}", diagnostic),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(argument_texts, vec!["`lib${expr}`", "\"asd \" + asd"]);
+    assert_eq!(
+      diagnostics[0].detailed_message(),
+      "unable to analyze dynamic import: `lib${expr}`"
+    );
+  }
+
+  #[test]
+  fn test_unfurling_wasm_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurler::new(
+      &mapped_resolver,
+      Some(&sloppy_imports_resolver),
+      true,
+    );
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import init from "lib/math.wasm";
+export { add } from "lib/math.wasm";
+const math = await import("lib/math.wasm");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import init from "./lib/math.wasm";
+export { add } from "./lib/math.wasm";
+const math = await import("./lib/math.wasm");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_non_registry_package_json_deps() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mut package_json = PackageJson::empty(cwd.join("package.json"));
+    package_json.dependencies = Some(IndexMap::from([
+      ("chalk".to_string(), "5".to_string()),
+      ("git-dep".to_string(), "github:denoland/git-dep".to_string()),
+      ("file-dep".to_string(), "file:../file-dep".to_string()),
+    ]));
+    let mapped_resolver = MappedSpecifierResolver::new(
+      None,
+      Arc::new(PackageJsonDepsProvider::new(Some(
+        get_local_package_json_version_reqs(&package_json),
+      ))),
+    );
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import chalk from "chalk";
+import gitDep from "git-dep";
+import fileDep from "file-dep/mod.js";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    let expected_source = r#"import chalk from "npm:chalk@5";
+import gitDep from "git-dep";
+import fileDep from "file-dep/mod.js";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    let schemes = diagnostics
+      .iter()
+      .map(|d| match d {
+        SpecifierUnfurlerDiagnostic::NonRegistryDependency {
+          scheme,
+          text_info,
+          range,
+          ..
+        } => (scheme.as_str(), text_info.range_text(range)),
+        _ => unreachable!("unexpected diagnostic: {:?}", d),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      schemes,
+      vec![("github", "git-dep"), ("file", "file-dep/mod.js")]
+    );
+  }
+
+  #[test]
+  fn test_values_to_set_versionless() {
+    let values = [
+      "jsr:@std/fs",
+      "npm:chalk",
+      "jsr:@std/path@>=1 <2",
+      "npm:/preact@>=10 <11/hooks",
+      "./local.ts",
+    ]
+    .map(|value| value.to_string());
+    let set = values_to_set(values.iter());
+    assert_eq!(
+      set,
+      HashSet::from([
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("chalk").unwrap()),
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/path").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("preact").unwrap()),
+      ])
+    );
+  }
+
+  #[test]
+  fn test_unfurling_relative_style() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let package_root = ModuleSpecifier::from_directory_path(&cwd).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";"#;
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .relative_style(RelativeStyle::DotSlash)
+      .package_root(package_root.clone())
+      .build();
+    let (unfurled_source, _) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, r#"import foo from "./lib/foo.ts";"#);
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .relative_style(RelativeStyle::BareFromRoot)
+      .package_root(package_root)
+      .build();
+    let (unfurled_source, _) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, r#"import foo from "/lib/foo.ts";"#);
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .relative_style(RelativeStyle::AbsoluteFileUrl)
+      .build();
+    let (unfurled_source, _) = unfurl_text(
+      &unfurler,
+      &specifier,
+      "import foo from \"lib/foo.ts\";\nimport chalk from \"chalk\";\n",
+    );
+    let foo_url =
+      ModuleSpecifier::from_file_path(cwd.join("lib/foo.ts")).unwrap();
+    assert_eq!(
+      unfurled_source,
+      format!(
+        "import foo from \"{}\";\nimport chalk from \"npm:chalk@5\";\n",
+        foo_url
+      )
+    );
+  }
+
+  #[test]
+  fn test_unfurling_self_import() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import self from "./mod.ts";
+import foo from "lib/foo.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"import self from "./mod.ts";
+import foo from "./lib/foo.ts";
+"#
+    );
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::SelfImport {
+        text_info, range, ..
+      } => {
+        assert_eq!(text_info.range_text(range), "./mod.ts");
+      }
+      diagnostic => unreachable!("unexpected diagnostic: {:?}", diagnostic),
+    }
+  }
+
+  #[test]
+  fn test_unfurling_dynamic_template_with_escapes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const a = await import(`l\x69b/${name}.ts`);
+const b = await import(`lib\u002F${name}.ts`);
+const lib = "lib/";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const a = await import(`./lib/${name}.ts`);
+const b = await import(`./lib/${name}.ts`);
+const lib = "lib/";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_typescript_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+import bar from "lib/bar.js";
+"#;
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .warn_typescript_imports(true)
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"import foo from "./lib/foo.ts";
+import bar from "./lib/bar.js";
+"#
+    );
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::TypeScriptImport {
+        resolved,
+        text_info,
+        range,
+        ..
+      } => {
+        assert!(resolved.path().ends_with("/lib/foo.ts"));
+        assert_eq!(text_info.range_text(range), "lib/foo.ts");
+      }
+      diagnostic => unreachable!("unexpected diagnostic: {:?}", diagnostic),
+    }
+  }
+
+  #[test]
+  fn test_resolve_one() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let referrer = ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    assert_eq!(
+      unfurler.resolve_one(&referrer, "express").as_deref(),
+      Some("npm:express@5")
+    );
+    assert_eq!(
+      unfurler.resolve_one(&referrer, "lib/foo.ts").as_deref(),
+      Some("./lib/foo.ts")
+    );
+    assert_eq!(unfurler.resolve_one(&referrer, "./lib/foo.ts"), None);
+  }
+
+  #[test]
+  fn test_unfurling_bom_and_shebang() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+
+    // the BOM is stripped from the text info, so the offsets of the text
+    // changes line up with the text they're applied to
+    let source_code = "\u{FEFF}import foo from \"lib/foo.ts\";\n";
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, "import foo from \"./lib/foo.ts\";\n");
+
+    let source_code = r#"#!/usr/bin/env -S deno run
+import foo from "lib/foo.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"#!/usr/bin/env -S deno run
+import foo from "./lib/foo.ts";
+"#
+    );
+  }
+
+  #[test]
+  fn test_unfurling_type_only_imports_and_exports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import type { Bar } from "lib/types.ts";
+export type { Foo } from "lib/types.ts";
+import { type X, Y } from "lib/mixed.ts";
+export { type Z } from "lib/mixed.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import type { Bar } from "./lib/types.ts";
+export type { Foo } from "./lib/types.ts";
+import { type X, Y } from "./lib/mixed.ts";
+export { type Z } from "./lib/mixed.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_collecting_remotes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import a from "https://deno.land/x/a/mod.ts";
+import b from "http://example.com/b.js";
+import a2 from "https://deno.land/x/a/mod.ts";
+import foo from "lib/foo.ts";
+import express from "express";
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let (unfurled_source, remotes) = unfurler.unfurl_collecting_remotes(
+      &specifier,
+      &source,
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import a from "https://deno.land/x/a/mod.ts";
+import b from "http://example.com/b.js";
+import a2 from "https://deno.land/x/a/mod.ts";
+import foo from "./lib/foo.ts";
+import express from "npm:express@5";
+"#
+    );
+    assert_eq!(
+      remotes,
+      vec![
+        ModuleSpecifier::parse("https://deno.land/x/a/mod.ts").unwrap(),
+        ModuleSpecifier::parse("http://example.com/b.js").unwrap(),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_unfurling_case_mismatch() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurler::new(
+      &mapped_resolver,
+      Some(&sloppy_imports_resolver),
+      true,
+    );
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "./B.ts";
+import b2 from "./b.ts";
+import index from "./Baz/Index.js";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"import b from "./b.ts";
+import b2 from "./b.ts";
+import index from "./baz/index.js";
+"#
+    );
+    let on_disk = diagnostics
+      .iter()
+      .map(|d| match d {
+        SpecifierUnfurlerDiagnostic::CaseMismatch {
+          on_disk,
+          text_info,
+          range,
+          ..
+        } => (
+          text_info.range_text(range).to_string(),
+          on_disk.path().rsplit('/').next().unwrap().to_string(),
+        ),
+        _ => unreachable!("unexpected diagnostic: {:?}", d),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      on_disk,
+      vec![
+        ("./B.ts".to_string(), "b.ts".to_string()),
+        ("./Baz/Index.js".to_string(), "index.js".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_unfurling_deep_relative_import() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("a/b/c/d/mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+import bar from "../bar.ts";
+"#;
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .max_relative_depth(Some(2))
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    // the rewrite still happens
+    assert!(unfurled_source.contains("../../../../lib/foo.ts"));
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::DeepRelativeImport {
+        depth,
+        text_info,
+        range,
+        ..
+      } => {
+        assert_eq!(*depth, 4);
+        assert_eq!(text_info.range_text(range), "lib/foo.ts");
+      }
+      diagnostic => unreachable!("unexpected diagnostic: {:?}", diagnostic),
+    }
+  }
+
+  #[test]
+  fn test_unfurl_source() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import express from "express";
+import foo from "lib/foo.ts";
+import fizz from "fizz";
+import chalk from "chalk";
+const test = await import("lib/foo.ts");
+"#;
+    let mut diagnostics = Vec::new();
+    let unfurled_source = unfurler.unfurl_source(
+      &specifier,
+      source_code,
+      MediaType::TypeScript,
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import express from "npm:express@5";
+import foo from "./lib/foo.ts";
+import fizz from "./fizz/mod.ts";
+import chalk from "npm:chalk@5";
+const test = await import("./lib/foo.ts");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+
+    // parse errors leave the source unchanged
+    let source_code = r#"import foo from "lib/foo.ts";
+const = 5;
+"#;
+    let mut diagnostics = Vec::new();
+    let unfurled_source = unfurler.unfurl_source(
+      &specifier,
+      source_code,
+      MediaType::TypeScript,
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(unfurled_source, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::ParseError { .. }
+    ));
+  }
+
+  #[test]
+  fn test_unfurling_import_map_alias_chains() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "a": "b",
+        "b": "c",
+        "c": "npm:c@1",
+        "cycle-a": "cycle-b",
+        "cycle-b": "cycle-a",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import a from "a";
+import b from "b";
+import cycle from "cycle-a";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    let expected_source = r#"import a from "npm:c@1";
+import b from "npm:c@1";
+import cycle from "cycle-a";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::ImportMapAliasCycle { chain, .. } => {
+        assert_eq!(chain, &["cycle-a", "cycle-b", "cycle-a"]);
+      }
+      diagnostic => unreachable!("unexpected diagnostic: {:?}", diagnostic),
+    }
+  }
+
+  #[test]
+  fn test_import_map_usage_tracker() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let mut usage_tracker = ImportMapUsageTracker::default();
+    let files = [
+      (
+        "a.ts",
+        r#"import express from "express";
+import foo from "lib/foo.ts";
+"#,
+      ),
+      ("b.ts", r#"import fizz from "fizz";"#),
+    ];
+    for (name, source_code) in files {
+      let specifier = ModuleSpecifier::from_file_path(cwd.join(name)).unwrap();
+      let source = parse_ast(&specifier, source_code);
+      unfurler.unfurl_tracking_import_map_usage(
+        &specifier,
+        &source,
+        &mut |_| {},
+        &mut usage_tracker,
+      );
+    }
+    let import_map = mapped_resolver.maybe_import_map().unwrap();
+    assert_eq!(
+      usage_tracker.unused(import_map),
+      vec!["@std/fs".to_string()]
+    );
+  }
+
+  #[test]
+  fn test_import_map_usage_tracker_records_resolved_key() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    // the scope's prefix match takes precedence over the exact match of the
+    // top level imports
+    let value = json!({
+      "imports": {
+        "lib/foo.ts": "./lib/bar.ts",
+      },
+      "scopes": {
+        "./": {
+          "lib/": "./lib/",
+        }
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let mut usage_tracker = ImportMapUsageTracker::default();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source = parse_ast(&specifier, r#"import foo from "lib/foo.ts";"#);
+    let unfurled_source = unfurler.unfurl_tracking_import_map_usage(
+      &specifier,
+      &source,
+      &mut |_| {},
+      &mut usage_tracker,
+    );
+    assert_eq!(unfurled_source, r#"import foo from "./lib/foo.ts";"#);
+    let import_map = mapped_resolver.maybe_import_map().unwrap();
+    assert_eq!(
+      usage_tracker.unused(import_map),
+      vec!["lib/foo.ts".to_string()]
+    );
+  }
+
+  #[test]
+  fn test_unfurling_protocol_relative_specifiers() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let source_code = r#"import lib from "//cdn.example.com/lib.js";"#;
+
+    // resolved against the scheme of a remote referrer
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::parse("https://deno.land/x/pkg/mod.ts").unwrap();
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let (unfurled_source, remotes) = unfurler.unfurl_collecting_remotes(
+      &specifier,
+      &source,
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import lib from "https://cdn.example.com/lib.js";"#
+    );
+    assert_eq!(
+      remotes,
+      vec![ModuleSpecifier::parse("https://cdn.example.com/lib.js").unwrap()]
+    );
+
+    // remote imports are reported when the scheme isn't allowed
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .allowed_schemes(Some(HashSet::from(["jsr".to_string()])))
+      .build();
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::DisallowedScheme { .. }
+    ));
+
+    // a file referrer makes it a file specifier on another host, which
+    // can't be made relative, so it's left as-is
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let (unfurled_source, _) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, source_code);
+  }
+
+  #[test]
+  fn test_unfurling_validate() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .validate(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";"#;
+
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, r#"import foo from "./lib/foo.ts";"#);
+
+    // a bad text change makes the original source be returned
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let text = unfurler.apply_text_changes(
+      &specifier,
+      &source,
+      vec![deno_ast::TextChange {
+        range: 0..6,
+        new_text: "imp(".to_string(),
+      }],
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(text, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::RewriteProducedInvalidSource { .. }
+    ));
+  }
+
+  #[test]
+  fn test_unfurling_directory_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .build();
+
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("dir_import/a.ts")).unwrap();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, r#"import { a } from ".";"#);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, r#"import { a } from "./mod.ts";"#);
+
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("dir_import/sub/a.ts")).unwrap();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, r#"import { a } from "..";"#);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, r#"import { a } from "./../mod.ts";"#);
+
+    // no entrypoint in the directory
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("no_entry/a.ts")).unwrap();
+    let source_code = r#"import { b } from ".";"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::UnresolvedDirectoryImport { .. }
+    ));
+  }
+
+  #[test]
+  fn test_try_unfurl_stops_on_break() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const a = await import(foo);
+const b = await import(bar);
+const c = await import(baz);
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let result = unfurler.try_unfurl(&specifier, &source, &mut |diagnostic| {
+      let is_unanalyzable = matches!(
+        diagnostic,
+        SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
+      );
+      diagnostics.push(diagnostic);
+      if is_unanalyzable {
+        ControlFlow::Break(())
+      } else {
+        ControlFlow::Continue(())
+      }
+    });
+    assert_eq!(result, ControlFlow::Break(()));
+    assert_eq!(diagnostics.len(), 1);
+
+    let result = unfurler
+      .try_unfurl(&specifier, &source, &mut |_| ControlFlow::Continue(()));
+    assert_eq!(result, ControlFlow::Continue(source_code.to_string()));
+  }
+
+  #[test]
+  fn test_unfurling_import_phases() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import defer * as foo from "lib/foo.ts";
+import source wasm from "lib/mod.wasm";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import defer * as foo from "./lib/foo.ts";
+import source wasm from "./lib/mod.wasm";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_owned() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = Arc::new(create_mapped_resolver(&cwd));
+    let sloppy_imports_resolver =
+      Arc::new(SloppyImportsResolver::new(Arc::new(RealFs)));
+    let unfurler: SpecifierUnfurler<'static> = SpecifierUnfurler::new_owned(
+      mapped_resolver,
+      Some(sloppy_imports_resolver),
+      true,
+    );
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import express from "express";
+import foo from "lib/foo.ts";
+import { b } from "./b.js";
+import "fs";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import express from "npm:express@5";
+import foo from "./lib/foo.ts";
+import { b } from "./b.ts";
+import "node:fs";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_dynamic_template_package_alias_prefix() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const a = await import(`express/${sub}`);
+const b = await import(`lib/${sub}`);
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const a = await import(`npm:express@5/${sub}`);
+const b = await import(`./lib/${sub}`);
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_analyze_dynamic_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+const test1 = await import("lib/foo.ts");
+const test2 = await import(`lib/foo.ts`);
+const test3 = await import(`lib/${expr}`);
+const test4 = await import(`./lib/${expr}`);
+const test5 = await import("./lib/something.ts");
+const test6 = await import(`./lib/something.ts`);
+// will warn
+const warn1 = await import(`lib${expr}`);
+const warn2 = await import(`${expr}`);
+"#;
+    let source = parse_ast(&specifier, source_code);
+    assert_eq!(
+      unfurler.analyze_dynamic_imports(&specifier, &source),
+      (6, 2)
+    );
+  }
+
+  #[test]
+  fn test_unfurling_dynamic_import_with_trailing_comment() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const a = await import("lib/foo.ts"); // "lib/foo.ts"
+const b = await import(/* "lib/foo.ts" */ "lib/foo.ts");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const a = await import("./lib/foo.ts"); // "lib/foo.ts"
+const b = await import(/* "lib/foo.ts" */ "./lib/foo.ts");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_jsdoc_import_tags() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.js")).unwrap();
+    let source_code = r#"/** @import { Foo, Bar } from "lib/types.ts" */
+/**
+ * @import * as ns from 'lib/types.ts'
+ */
+/** @import "lib/types.ts" */
+/** @type {import("lib/types.ts").Foo} */
+export const foo = {};
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"/** @import { Foo, Bar } from "./lib/types.ts" */
+/**
+ * @import * as ns from './lib/types.ts'
+ */
+/** @import "./lib/types.ts" */
+/** @type {import("./lib/types.ts").Foo} */
+export const foo = {};
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_diagnostic_lsp_range() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = "// 🦕\nconst café = \"🦕\"; import \"fs\";\n";
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::BareNodeBuiltin { .. }
+    ));
+    // the dinosaur is two UTF-16 code units and the é is one
+    assert_eq!(
+      diagnostics[0].lsp_range(),
+      lsp_types::Range {
+        start: lsp_types::Position {
+          line: 1,
+          character: 27,
+        },
+        end: lsp_types::Position {
+          line: 1,
+          character: 29,
+        },
+      }
+    );
+  }
+
+  #[test]
+  fn test_unfurling_node_builtin_submodules() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .bare_node_builtins(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { readFile } from "fs/promises";
+import { ReadableStream } from "stream/web";
+import { isDate } from "util/types";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import { readFile } from "node:fs/promises";
+import { ReadableStream } from "node:stream/web";
+import { isDate } from "node:util/types";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_ignored_codes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .ignored_codes(HashSet::from(["unanalyzable-dynamic-import"]))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const test1 = await import("lib/foo.ts");
+// will warn
+const warn1 = await import(`lib${expr}`);
+const warn2 = await import(`${expr}`);
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const test1 = await import("./lib/foo.ts");
+// will warn
+const warn1 = await import(`lib${expr}`);
+const warn2 = await import(`${expr}`);
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_deno_json_deps_non_string_values() {
+    let config = deno_config::ConfigFile::new(
+      r#"{
+  // the imports
+  "imports": {
+    "@std/fs": "jsr:@std/fs@1", // the jsr package
+    "port": 8000,
+    "blocked": null,
+    "list": ["npm:express@5"],
+    /* the npm package */
+    "chalk": "npm:chalk@5"
+  },
+  "scopes": {
+    "./vendor/": {
+      "blocked": null,
+      "preact": "npm:preact@10"
+    },
+    "./invalid/": 1
+  }
+}"#,
+      ModuleSpecifier::parse("file:///dir/deno.jsonc").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      deno_json_deps(&config),
+      HashSet::from([
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("chalk@5").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("preact@10").unwrap()),
+      ])
+    );
+  }
+
+  #[test]
+  fn test_unfurling_output_base() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+import { b } from "./b.ts";
+import express from "express";
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let unfurled_source = unfurler.unfurl_with_options(
+      &specifier,
+      &source,
+      &UnfurlOptions {
+        output_base: Some(
+          ModuleSpecifier::from_directory_path(cwd.join("dist")).unwrap(),
+        ),
+        ..Default::default()
+      },
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import foo from "./../lib/foo.ts";
+import { b } from "./../b.ts";
+import express from "npm:express@5";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_empty_specifiers() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    for source_code in [r#"import x from "";"#, r#"import x from "  ";"#] {
+      let (unfurled_source, diagnostics) =
+        unfurl_text(&unfurler, &specifier, source_code);
+      assert_eq!(unfurled_source, source_code);
+      assert_eq!(diagnostics.len(), 1);
+      assert!(matches!(
+        diagnostics[0],
+        SpecifierUnfurlerDiagnostic::EmptySpecifier { .. }
+      ));
+    }
+  }
+
+  #[test]
+  fn test_unfurling_dynamic_import_with_options() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const a = await import("lib/x.json", { with: { type: "json" } });
+const b = await import(`lib/${name}.json`, { with: { type: "json" } });
+const c = await import("lib/x.json", { with: { "lib/x.json": "json" } });
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const a = await import("./lib/x.json", { with: { type: "json" } });
+const b = await import(`./lib/${name}.json`, { with: { type: "json" } });
+const c = await import("./lib/x.json", { with: { "lib/x.json": "json" } });
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_deps_from_json() {
+    let value = json!({
+      "imports": {
+        "@std/fs": "jsr:@std/fs@1",
+        "chalk": "npm:chalk@5",
+        "lib/": "./lib/",
+      },
+      "scopes": {
+        "./vendor/": {
+          "preact": "npm:preact@10",
+        },
+      },
+    });
+    assert_eq!(
+      deps_from_json(&value),
+      HashSet::from([
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("chalk@5").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("preact@10").unwrap()),
+      ])
+    );
+    assert_eq!(deps_from_json(&json!({})), HashSet::new());
+  }
+
+  #[test]
+  fn test_detect_jsr_npm_duplicates() {
+    let deps = values_to_set(
+      [
+        "jsr:@std/fs@1",
+        "npm:@jsr/std__fs@1",
+        "jsr:@std/path@1",
+        "npm:@jsr/std__assert@1",
+        "npm:chalk@5",
+      ]
+      .map(|value| value.to_string())
+      .iter(),
+    );
+    assert_eq!(
+      detect_jsr_npm_duplicates(&deps),
+      vec![(
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("@jsr/std__fs@1").unwrap()),
+      )]
+    );
+  }
+
+  #[test]
+  fn test_unfurling_correct_relative_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { a } from "./existing/mod.ts";
+import { b } from "./existing/util.js";
+import { c } from "./existing/esm.mjs";
+const d = await import("./existing/mod.ts");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, source_code);
+    for specifier_text in [
+      "./existing/mod.ts",
+      "./existing/util.js",
+      "./existing/esm.mjs",
+    ] {
+      assert_eq!(unfurler.resolve_one(&specifier, specifier_text), None);
+    }
+  }
+
+  #[test]
+  fn test_unfurling_remapped_urls() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "https://deno.land/std@0.200.0/fs/mod.ts": "jsr:@std/fs@1",
+        "https://deno.land/std@0.200.0/path/": "jsr:/@std/path@1/",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurler::new(&mapped_resolver, None, false);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { walk } from "https://deno.land/std@0.200.0/fs/mod.ts";
+import { join } from "https://deno.land/std@0.200.0/path/join.ts";
+import { assert } from "https://deno.land/std@0.200.0/assert/mod.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import { walk } from "jsr:@std/fs@1";
+import { join } from "jsr:/@std/path@1/join.ts";
+import { assert } from "https://deno.land/std@0.200.0/assert/mod.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurl_package() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let clean = ModuleSpecifier::from_file_path(cwd.join("a.ts")).unwrap();
+    let warning = ModuleSpecifier::from_file_path(cwd.join("b.ts")).unwrap();
+    let files = vec![
+      (
+        clean.clone(),
+        parse_ast(&clean, r#"import foo from "lib/foo.ts";"#),
+      ),
+      (
+        warning.clone(),
+        parse_ast(&warning, r#"const a = await import(`${expr}`);"#),
+      ),
+    ];
+    let results = unfurler.unfurl_package(&files);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].specifier, clean);
+    assert_eq!(results[0].text, r#"import foo from "./lib/foo.ts";"#);
+    assert_eq!(results[0].diagnostics.len(), 0);
+    assert_eq!(results[1].specifier, warning);
+    assert_eq!(results[1].text, r#"const a = await import(`${expr}`);"#);
+    assert_eq!(results[1].diagnostics.len(), 1);
+    assert!(matches!(
+      results[1].diagnostics[0],
+      SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
+    ));
+  }
+
+  #[test]
+  fn test_unfurling_trailing_slash_file() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "b": "./b.ts/",
+        "baz": "./baz/",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .resolve_sloppy_imports(false)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { b } from "b";
+const baz = await import("baz");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    // a directory keeps its trailing slash
+    let expected_source = r#"import { b } from "./b.ts";
+const baz = await import("./baz/");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_jsx_pragmas() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.tsx")).unwrap();
+    let source_code = r#"/** @jsxImportSource lib/jsx */
+/** @jsxRuntime automatic */
+/** @jsxFrag Fragment */
+export const a = <div />;
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"/** @jsxImportSource ./lib/jsx */
+/** @jsxRuntime automatic */
+/** @jsxFrag Fragment */
+export const a = <div />;
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_sloppy_imports_are_errors() {
+    use deno_ast::diagnostics::Diagnostic;
+    use deno_ast::diagnostics::DiagnosticLevel;
+
+    use crate::tools::registry::diagnostics::PublishDiagnostic;
+
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .sloppy_imports_are_errors(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "./b";
+import b2 from "./b.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    let expected_source = r#"import b from "./b.ts";
+import b2 from "./b.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::SloppyImportRewrite { .. }
+    ));
+    assert_eq!(diagnostics[0].suggested_fix().as_deref(), Some("./b.ts"));
+    let diagnostic = PublishDiagnostic::SpecifierUnfurl(diagnostics[0].clone());
+    assert!(matches!(diagnostic.level(), DiagnosticLevel::Error));
+  }
+
+  #[test]
+  fn test_unfurling_import_meta_url_assets() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const css = new URL("styles/app.css", import.meta.url);
+const wasm = await fetch(new URL("lib/mod.wasm", import.meta.url).href);
+const relative = new URL("./styles/app.css", import.meta.url);
+const expr = new URL(name, import.meta.url);
+const other = new URL("styles/app.css", "https://example.com/");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const css = new URL("./styles/app.css", import.meta.url);
+const wasm = await fetch(new URL("./lib/mod.wasm", import.meta.url).href);
+const relative = new URL("./styles/app.css", import.meta.url);
+const expr = new URL(name, import.meta.url);
+const other = new URL("styles/app.css", "https://example.com/");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_specifier_transform() {
+    struct ScopeTransform;
+
+    impl SpecifierTransform for ScopeTransform {
+      fn transform(&self, resolved: &ModuleSpecifier) -> Option<String> {
+        resolved
+          .as_str()
+          .strip_prefix("npm:@internal/")
+          .map(|rest| format!("npm:@acme/{rest}"))
+      }
+    }
+
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "@internal/foo": "npm:@internal/foo@1",
+        "lib/": "./lib/",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let transform = ScopeTransform;
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .specifier_transform(Some(&transform))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "@internal/foo";
+import bar from "lib/bar.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import foo from "npm:@acme/foo@1";
+import bar from "./lib/bar.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_ambiguous_import_map_matches() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "lib/": "./lib/",
+        "lib/foo.ts": "./lib/foo_impl.ts",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .warn_ambiguous_import_map_matches(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+import bar from "lib/bar.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    let expected_source = r#"import foo from "./lib/foo_impl.ts";
+import bar from "./lib/bar.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    assert_eq!(diagnostics.len(), 1);
+    let SpecifierUnfurlerDiagnostic::AmbiguousImportMapMatch { keys, .. } =
+      &diagnostics[0]
+    else {
+      panic!("unexpected diagnostic: {:?}", diagnostics[0]);
+    };
+    let mut keys = keys.clone();
+    keys.sort();
+    assert_eq!(keys, vec!["lib/", "lib/foo.ts"]);
+  }
+
+  #[test]
+  fn test_unfurling_types_pragmas() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"// @deno-types="lib/foo.d.ts"
+import foo from "lib/foo.js";
+// @ts-types="lib/bar.d.ts"
+import bar from "lib/bar.js";
+// @ts-types='lib/baz.d.ts'
+export * from "lib/baz.js";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"// @deno-types="./lib/foo.d.ts"
+import foo from "./lib/foo.js";
+// @ts-types="./lib/bar.d.ts"
+import bar from "./lib/bar.js";
+// @ts-types='./lib/baz.d.ts'
+export * from "./lib/baz.js";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurl_cached() {
+    struct CountingTransform(Cell<usize>);
+
+    impl SpecifierTransform for CountingTransform {
+      fn transform(&self, _resolved: &ModuleSpecifier) -> Option<String> {
+        self.0.set(self.0.get() + 1);
+        None
+      }
+    }
+
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let transform = CountingTransform(Cell::new(0));
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .specifier_transform(Some(&transform))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let parse = |source_code: &str| parse_ast(&specifier, source_code);
+    let mut cache = UnfurlCache::default();
+    let mut diagnostics = Vec::new();
+
+    let source = parse("import foo from \"lib/foo.ts\";\n");
+    let unfurled =
+      unfurler.unfurl_cached(&mut cache, &specifier, &source, &mut |d| {
+        diagnostics.push(d)
+      });
+    assert_eq!(unfurled, "import foo from \"./lib/foo.ts\";\n");
+    assert_eq!(transform.0.get(), 1);
+
+    // same content, so it's not analyzed again
+    let source = parse("import foo from \"lib/foo.ts\";\n");
+    let unfurled =
+      unfurler.unfurl_cached(&mut cache, &specifier, &source, &mut |d| {
+        diagnostics.push(d)
+      });
+    assert_eq!(unfurled, "import foo from \"./lib/foo.ts\";\n");
+    assert_eq!(transform.0.get(), 1);
+
+    // changed content
+    let source = parse("import bar from \"lib/bar.ts\";\n");
+    let unfurled =
+      unfurler.unfurl_cached(&mut cache, &specifier, &source, &mut |d| {
+        diagnostics.push(d)
+      });
+    assert_eq!(unfurled, "import bar from \"./lib/bar.ts\";\n");
+    assert_eq!(transform.0.get(), 2);
+    assert_eq!(diagnostics.len(), 0);
+  }
+
+  #[test]
+  fn test_unfurling_backslash_specifiers() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from ".\\lib\\foo.ts";
+import bar from "./lib/bar.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(
+      matches!(
+        diagnostics[0],
+        SpecifierUnfurlerDiagnostic::BackslashInSpecifier { .. }
+      ),
+      "{:?}",
+      diagnostics[0]
+    );
+    let expected_source = r#"import foo from "./lib/foo.ts";
+import bar from "./lib/bar.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurl_with_stats_node_builtins() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .bare_node_builtins(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import url from "url";
+import { readFile } from "fs/promises";
+import path from "node:path";
+import foo from "lib/foo.ts";
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let (unfurled_source, stats) =
+      unfurler
+        .unfurl_with_stats(&specifier, &source, &mut |d| diagnostics.push(d));
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import url from "node:url";
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import foo from "./lib/foo.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    assert_eq!(
+      stats.node_builtins,
+      HashSet::from(["url".to_string(), "fs".to_string()])
+    );
+  }
+
+  #[test]
+  fn test_relative_url_npm_subpaths() {
+    let referrer = ModuleSpecifier::parse("file:///project/mod.ts").unwrap();
+    for resolved in [
+      "npm:foo@1/bar",
+      "npm:foo@1/sub/path",
+      "npm:@scope/foo@1/a/b/c.js",
+    ] {
+      let resolved_url = ModuleSpecifier::parse(resolved).unwrap();
+      assert_eq!(
+        relative_url(&resolved_url, &referrer).as_deref(),
+        Some(resolved)
+      );
+    }
+  }
+
+  #[test]
+  fn test_unfurling_npm_subpath_targets() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "foo/bar": "npm:foo@1/bar",
+        "foo/deep": "npm:foo@1/sub/path",
+        "foo/": "npm:/foo@1/",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import bar from "foo/bar";
+import deep from "foo/deep";
+import other from "foo/other/nested.js";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import bar from "npm:foo@1/bar";
+import deep from "npm:foo@1/sub/path";
+import other from "npm:/foo@1/other/nested.js";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_diagnostic_code_round_trip() {
+    let mut seen = HashSet::new();
+    for code in DiagnosticCode::ALL {
+      let text = code.to_string();
+      assert_eq!(text, code.as_str());
+      assert_eq!(text.parse::<DiagnosticCode>().unwrap(), *code);
+      assert!(seen.insert(text), "duplicate code '{}'", code);
+    }
+    assert!("not-a-code".parse::<DiagnosticCode>().is_err());
+
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let (_, diagnostics) =
+      unfurl_text(&unfurler, &specifier, "import \"\";\nimport \"fs\";\n");
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in &diagnostics {
+      assert_eq!(diagnostic.code_enum().as_str(), diagnostic.code());
+    }
+    assert_eq!(diagnostics[0].code_enum(), DiagnosticCode::EmptySpecifier);
+    assert_eq!(diagnostics[1].code_enum(), DiagnosticCode::BareNodeBuiltin);
+  }
+
+  #[test]
+  fn test_unfurling_sloppy_extension_preference() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    // both b.ts and b.js exist in this directory
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("ext_preference/mod.ts"))
+        .unwrap();
+    let source_code = "import { b } from \"./b\";\n";
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, "import { b } from \"./b.ts\";\n");
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .sloppy_extension_preference(Some(vec![
+        MediaType::JavaScript,
+        MediaType::TypeScript,
+      ]))
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, "import { b } from \"./b.js\";\n");
+  }
+
+  #[test]
+  fn test_unfurling_text_and_bytes_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    // the attribute type doesn't affect how the specifier is unfurled
+    let source_code = r#"import t from "lib/note.txt" with { type: "text" };
+import b from "lib/image.png" with { type: "bytes" };
+export { default as c } from "lib/data.bin" with { type: "bytes" };
+const d = await import("lib/other.txt", { with: { type: "text" } });
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import t from "./lib/note.txt" with { type: "text" };
+import b from "./lib/image.png" with { type: "bytes" };
+export { default as c } from "./lib/data.bin" with { type: "bytes" };
+const d = await import("./lib/other.txt", { with: { type: "text" } });
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_chained_dynamic_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const a = await import("lib/x.ts").then(m => m.default);
+const b = await import(`lib/${name}.ts`).then((m) => m.default);
+import("lib/y.ts").then(({ y }) => y("lib/y.ts")).catch(() => {});
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const a = await import("./lib/x.ts").then(m => m.default);
+const b = await import(`./lib/${name}.ts`).then((m) => m.default);
+import("./lib/y.ts").then(({ y }) => y("lib/y.ts")).catch(() => {});
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_only_static_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.js")).unwrap();
+    let source_code = r#"/// <reference types="lib/types.d.ts" />
+import foo from "lib/foo.ts";
+/** @type {import("lib/bar.ts").Bar} */
+let bar;
+const baz = await import("lib/baz.ts");
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let unfurled_source = unfurler.unfurl_with_options(
+      &specifier,
+      &source,
+      &UnfurlOptions {
+        kinds: DependencyKinds::STATIC,
+        ..Default::default()
+      },
+      &mut |d| diagnostics.push(d),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"/// <reference types="lib/types.d.ts" />
+import foo from "./lib/foo.ts";
+/** @type {import("lib/bar.ts").Bar} */
+let bar;
+const baz = await import("lib/baz.ts");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+
+    let unfurled_source = unfurler.unfurl_with_options(
+      &specifier,
+      &source,
+      &UnfurlOptions {
+        kinds: DependencyKinds::STATIC | DependencyKinds::JSDOC,
+        ..Default::default()
+      },
+      &mut |d| diagnostics.push(d),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"/// <reference types="lib/types.d.ts" />
+import foo from "./lib/foo.ts";
+/** @type {import("./lib/bar.ts").Bar} */
+let bar;
+const baz = await import("lib/baz.ts");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    assert!(DependencyKinds::default().contains(DependencyKinds::JSDOC));
+  }
+
+  #[test]
+  fn test_unfurling_specifiers_with_apostrophes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import a from "lib/o'brien.ts";
+import b from "lib/'quoted'";
+export * from "lib/it's";
+const c = await import("lib/o'brien.ts");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import a from "./lib/o'brien.ts";
+import b from "./lib/'quoted'";
+export * from "./lib/it's";
+const c = await import("./lib/o'brien.ts");
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_deno_json_deps_with_import_map() {
+    let config = deno_config::ConfigFile::new(
+      r#"{
+  "importMap": "./import_map.json",
+  "imports": {}
+}"#,
+      ModuleSpecifier::parse("file:///dir/deno.json").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(deno_json_deps(&config), HashSet::new());
+    let import_map_value = json!({
+      "imports": {
+        "@std/fs": "jsr:@std/fs@1",
+        "lib/": "./lib/",
+      },
+      "scopes": {
+        "./vendor/": {
+          "chalk": "npm:chalk@5",
+        },
+      },
+    });
+    assert_eq!(
+      deno_json_deps_with_import_map(&config, &import_map_value),
+      HashSet::from([
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("chalk@5").unwrap()),
+      ])
+    );
+
+    // the inline imports are included too
+    let config = deno_config::ConfigFile::new(
+      r#"{
+  "importMap": "./import_map.json",
+  "imports": {
+    "preact": "npm:preact@10"
+  }
+}"#,
+      ModuleSpecifier::parse("file:///dir/deno.json").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      deno_json_deps_with_import_map(&config, &import_map_value),
+      HashSet::from([
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("chalk@5").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("preact@10").unwrap()),
+      ])
+    );
+  }
+
+  #[test]
+  fn test_unfurling_cross_export_internal_imports() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let a_export =
+      ModuleSpecifier::from_file_path(cwd.join("a/mod.ts")).unwrap();
+    let b_export =
+      ModuleSpecifier::from_file_path(cwd.join("b/mod.ts")).unwrap();
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .exports(vec![a_export.clone(), b_export.clone()])
+      .build();
+    let source_code = r#"import internal from "./internal.ts";
+import b from "../b/mod.ts";
+import bInternal from "../b/internal/util.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &a_export, source_code);
+    // the import is still unfurled
+    assert_eq!(
+      unfurled_source,
+      r#"import internal from "./internal.ts";
+import b from "./../b/mod.ts";
+import bInternal from "./../b/internal/util.ts";
+"#
+    );
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::CrossExportInternalImport {
+        export, ..
+      } => {
+        assert_eq!(export, &b_export);
+      }
+      diagnostic => panic!("unexpected diagnostic: {:?}", diagnostic),
+    }
+
+    // so are imports from modules outside of any export's directory
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let (_, diagnostics) = unfurl_text(
+      &unfurler,
+      &specifier,
+      "import a from \"./a/internal.ts\";\n",
+    );
+    assert_eq!(diagnostics.len(), 1);
+  }
+
+  #[test]
+  fn test_unfurling_import_meta_url_native_libraries() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    // these aren't modules and don't need to exist to be unfurled
+    let source_code = r#"const lib = Deno.dlopen(
+  new URL("lib/native.dylib", import.meta.url),
+  {},
+);
+const so = new URL("lib/native.so", import.meta.url);
+const dll = new URL("lib/native.dll", import.meta.url);
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const lib = Deno.dlopen(
+  new URL("./lib/native.dylib", import.meta.url),
+  {},
+);
+const so = new URL("./lib/native.so", import.meta.url);
+const dll = new URL("./lib/native.dll", import.meta.url);
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_to_range_clamps_out_of_bounds_positions() {
+    let specifier = ModuleSpecifier::parse("file:///mod.ts").unwrap();
+    let parsed_source =
+      parse_ast(&specifier, "import foo from \"./foo.ts\";\nfoo();\n");
+    let text_len = parsed_source.text_info().text_str().len();
+    let position = |line, character| deno_graph::Position { line, character };
+
+    // a line past the end of the source
+    let range = to_range(
+      &parsed_source,
+      &deno_graph::PositionRange {
+        start: position(100, 0),
+        end: position(200, 5),
+      },
+    );
+    assert_eq!(range, text_len..text_len);
+
+    // a character past the end of the line
+    let range = to_range(
+      &parsed_source,
+      &deno_graph::PositionRange {
+        start: position(1, 0),
+        end: position(1, 100),
+      },
+    );
+    assert_eq!(range, 28..34);
+
+    // an end before the start
+    let range = to_range(
+      &parsed_source,
+      &deno_graph::PositionRange {
+        start: position(1, 2),
+        end: position(0, 0),
+      },
+    );
+    assert_eq!(range, 30..30);
+  }
+
+  #[test]
+  fn test_unfurling_report_only() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .report_only(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+const a = await import("lib/foo.ts");
+const b = await import(`lib${expr}`);
+const c = await import(expr);
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, source_code);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| matches!(
+      d,
+      SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
+    )));
+  }
+
+  #[test]
+  fn test_unfurling_identity_import_map_entries() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "npm:chalk@5": "npm:chalk@5",
+        "jsr:@std/fs@1": "jsr:@std/fs@1",
+        "https://deno.land/x/mod.ts": "https://deno.land/x/mod.ts",
+        "./lib/foo.ts": "./lib/foo.ts",
+        // the value is a url relative to the deno.json
+        "a": "./a",
+      }
+    });
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import chalk from "npm:chalk@5";
+import * as fs from "jsr:@std/fs@1";
+import mod from "https://deno.land/x/mod.ts";
+import foo from "./lib/foo.ts";
+import a from "a";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import chalk from "npm:chalk@5";
+import * as fs from "jsr:@std/fs@1";
+import mod from "https://deno.land/x/mod.ts";
+import foo from "./lib/foo.ts";
+import a from "./a";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+
+    // unfurling the unfurled source is a no-op
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, expected_source);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_leaves_jsx_attributes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    for file_name in ["mod.tsx", "mod.jsx"] {
+      let specifier =
+        ModuleSpecifier::from_file_path(cwd.join(file_name)).unwrap();
+      let source_code = r#"/** @jsxImportSource lib/jsx */
+import { Logo } from "lib/logo.ts";
+export const a = (
+  <div>
+    <img src="lib/logo.png" alt="lib/foo.ts" />
+    <Logo href={"lib/bar.ts"} import="lib/baz.ts">lib/qux.ts</Logo>
+  </div>
+);
+const b = await import("lib/foo.ts");
+"#;
+      let (unfurled_source, diagnostics) =
+        unfurl_text(&unfurler, &specifier, source_code);
+      assert_eq!(diagnostics.len(), 0);
+      let expected_source = r#"/** @jsxImportSource ./lib/jsx */
+import { Logo } from "./lib/logo.ts";
+export const a = (
+  <div>
+    <img src="lib/logo.png" alt="lib/foo.ts" />
+    <Logo href={"lib/bar.ts"} import="lib/baz.ts">lib/qux.ts</Logo>
+  </div>
+);
+const b = await import("./lib/foo.ts");
+"#;
+      assert_eq!(unfurled_source, expected_source, "{}", file_name);
+    }
+  }
+
+  #[test]
+  fn test_unfurling_jsr_version_ranges_with_subpaths() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    // deno.json imports are expanded like this, which adds the `@std/fs/`
+    // style entries that subpaths resolve with
+    let value = import_map::ext::expand_import_map_value(json!({
+      "imports": {
+        "@std/fs": "jsr:@std/fs@^1.0.0",
+        "@std/path": "jsr:@std/path@~1.2.0",
+        "@std/http": "jsr:@std/http@1.0.3",
+      }
+    }));
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import * as fs from "@std/fs";
+import { walk } from "@std/fs/walk.ts";
+import { join } from "@std/path/posix/join.ts";
+import { serve } from "@std/http/server.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import * as fs from "jsr:@std/fs@^1.0.0";
+import { walk } from "jsr:/@std/fs@^1.0.0/walk.ts";
+import { join } from "jsr:/@std/path@~1.2.0/posix/join.ts";
+import { serve } from "jsr:/@std/http@1.0.3/server.ts";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+    for line in unfurled_source.lines() {
+      let (_, specifier) = line.split_once('"').unwrap();
+      let specifier = specifier.trim_end_matches("\";");
+      assert!(
+        JsrPackageReqReference::from_str(specifier).is_ok(),
+        "{}",
+        specifier
+      );
+    }
+  }
+
+  #[test]
+  fn test_unfurling_reports_import_map_diagnostics() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = json!({
+      "imports": {
+        "lib/": "./lib/",
+        "invalid": 1,
+        "no-slash/": "./no-slash",
+      }
+    });
+    let ImportMapWithDiagnostics {
+      import_map,
+      diagnostics,
+    } = import_map::parse_from_value(deno_json_url.clone(), value).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let mut reported = Vec::new();
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .report_import_map_diagnostics(&deno_json_url, &diagnostics, &mut |d| {
+        reported.push(d)
+      })
+      .build();
+    assert_eq!(reported.len(), 2);
+    for (diagnostic, import_map_diagnostic) in reported.iter().zip(&diagnostics)
+    {
+      match diagnostic {
+        SpecifierUnfurlerDiagnostic::ImportMapDiagnostic {
+          specifier,
+          message,
+        } => {
+          assert_eq!(specifier, &deno_json_url);
+          assert_eq!(message, &import_map_diagnostic.to_string());
+        }
+        diagnostic => panic!("unexpected diagnostic: {:?}", diagnostic),
+      }
+    }
+
+    // the valid entries still resolve
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, "import foo from \"lib/foo.ts\";\n");
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, "import foo from \"./lib/foo.ts\";\n");
+  }
+
+  #[test]
+  fn test_unfurling_explicit_file_specifiers() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let inside_url =
+      ModuleSpecifier::from_file_path(cwd.join("lib/foo.ts")).unwrap();
+    let outside_url =
+      ModuleSpecifier::from_file_path(cwd.parent().unwrap().join("outside.ts"))
+        .unwrap();
+    let source_code = format!(
+      "import foo from \"{}\";\nimport outside from \"{}\";\n",
+      inside_url, outside_url
+    );
+    let expected_source = "import foo from \"./lib/foo.ts\";\nimport outside from \"./../outside.ts\";\n";
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, &source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(unfurled_source, expected_source);
+
+    // there's no diagnostic specifically for leaving the package, but
+    // going up directories can be reported
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .max_relative_depth(Some(0))
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, &source_code);
+    assert_eq!(unfurled_source, expected_source);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::DeepRelativeImport { depth: 1, .. }
+    ));
+  }
+
+  #[test]
+  fn test_diagnostic_suggested_fix() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { readFile } from "fs/promises";
+const a = await import(expr);
+"#;
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::BareNodeBuiltin { .. }
+    ));
+    assert_eq!(
+      diagnostics[0].suggested_fix().as_deref(),
+      Some("node:fs/promises")
+    );
+    assert!(matches!(
+      diagnostics[1],
+      SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
+    ));
+    assert_eq!(diagnostics[1].suggested_fix(), None);
+  }
+
+  #[test]
+  fn test_unfurling_scoped_jsr_packages() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let deno_json_url =
+      ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
+    let value = import_map::ext::expand_import_map_value(json!({
+      "imports": {
+        "@myorg/lib": "jsr:@myorg/lib@1",
+        "@myorg/library": "jsr:@myorg/library@2",
+        "@myorg/lib-extra": "jsr:@myorg/lib-extra@3",
+      }
+    }));
+    let ImportMapWithDiagnostics { import_map, .. } =
+      import_map::parse_from_value(deno_json_url, value).unwrap();
+    let mapped_resolver = MappedSpecifierResolver::new(
+      Some(Arc::new(import_map)),
+      Arc::new(PackageJsonDepsProvider::new(None)),
+    );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import "@myorg/lib";
+import "@myorg/lib/sub";
+import "@myorg/lib/sub/deep.ts";
+import "@myorg/library";
+import "@myorg/library/sub";
+import "@myorg/lib-extra/sub";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"import "jsr:@myorg/lib@1";
+import "jsr:/@myorg/lib@1/sub";
+import "jsr:/@myorg/lib@1/sub/deep.ts";
+import "jsr:@myorg/library@2";
+import "jsr:/@myorg/library@2/sub";
+import "jsr:/@myorg/lib-extra@3/sub";
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_sort_diagnostics() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.js")).unwrap();
+    // the jsdoc import is analyzed after the dependencies, but comes first
+    let source_code = r#"/** @type {import("fs").Stats} */
+let stats;
+import "";
+const a = await import(expr);
+"#;
+    let (_, mut diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    let codes = |diagnostics: &[SpecifierUnfurlerDiagnostic]| {
+      diagnostics.iter().map(|d| d.code()).collect::<Vec<_>>()
+    };
+    assert_eq!(
+      codes(&diagnostics),
+      vec![
+        "empty-specifier",
+        "unanalyzable-dynamic-import",
+        "bare-node-builtin"
+      ]
+    );
+    sort_diagnostics(&mut diagnostics);
+    assert_eq!(
+      codes(&diagnostics),
+      vec![
+        "bare-node-builtin",
+        "empty-specifier",
+        "unanalyzable-dynamic-import"
+      ]
+    );
+  }
+
+  #[test]
+  fn test_unfurling_css() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("styles.css")).unwrap();
+    let source_code = r#"@import "lib/base.css";
+@import url('lib/theme.css');
+/* @import "lib/old.css"; */
+.a { background: url(lib/bg.png); }
+.b { background: url("data:image/png;base64,AAAA"); }
+.c { fill: url(#gradient); }
+.d { background: url("./bg.png"); }
+"#;
+    let mut diagnostics = Vec::new();
+    let unfurled_source =
+      unfurler.unfurl_css(&specifier, source_code, &mut |diagnostic| {
+        diagnostics.push(diagnostic)
+      });
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"@import "./lib/base.css";
+@import url('./lib/theme.css');
+/* @import "lib/old.css"; */
+.a { background: url(./lib/bg.png); }
+.b { background: url("data:image/png;base64,AAAA"); }
+.c { fill: url(#gradient); }
+.d { background: url("./bg.png"); }
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_check_file_existence() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let fs = Arc::new(RealFs);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "./b.ts";
+import sloppy from "./b";
+import missing from "./does-not-exist.ts";
+"#;
+
+    // off by default
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .build();
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .check_file_existence(true)
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"import b from "./b.ts";
+import sloppy from "./b.ts";
+import missing from "./does-not-exist.ts";
+"#
+    );
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::MissingFile {
+        resolved,
+        text_info,
+        range,
+        ..
+      } => {
+        assert_eq!(text_info.range_text(range), "./does-not-exist.ts");
+        assert_eq!(
+          resolved,
+          &ModuleSpecifier::from_file_path(cwd.join("does-not-exist.ts"))
+            .unwrap()
+        );
+      }
+      diagnostic => unreachable!("unexpected diagnostic: {:?}", diagnostic),
+    }
+  }
+
+  #[test]
+  fn test_unfurling_leaves_tagged_templates() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    // only the ranges reported by the module analyzer are rewritten, so
+    // specifier-like text in tagged templates is left alone
+    let source_code = r#"import foo from "lib/foo.ts";
+const a = html`<script type="module">import "lib/foo.ts";</script>`;
+const b = css`@import "lib/base.css";`;
+const c = load`lib/foo.ts`;
+const d = String.raw`import("express")`;
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import foo from "./lib/foo.ts";
+const a = html`<script type="module">import "lib/foo.ts";</script>`;
+const b = css`@import "lib/base.css";`;
+const c = load`lib/foo.ts`;
+const d = String.raw`import("express")`;
+"#
+    );
+  }
+
+  #[test]
+  fn test_missing_declared_deps() {
+    let declared = deps_from_json(&json!({
+      "imports": {
+        "@std/fs": "jsr:@std/fs@1",
+        "chalk": "npm:chalk@5",
+      }
+    }));
+    let used = HashSet::from([
+      JsrDepPackageReq::jsr(PackageReq::from_str("@std/fs@^1.0.0").unwrap()),
+      JsrDepPackageReq::npm(PackageReq::from_str("chalk@5").unwrap()),
+      JsrDepPackageReq::npm(PackageReq::from_str("express@5").unwrap()),
+      // same name as a declared dependency, but from the other registry
+      JsrDepPackageReq::jsr(PackageReq::from_str("@std/path@1").unwrap()),
+      JsrDepPackageReq::npm(PackageReq::from_str("@std/fs@1").unwrap()),
+    ]);
+    assert_eq!(
+      missing_declared_deps(&used, &declared),
+      vec![
+        JsrDepPackageReq::jsr(PackageReq::from_str("@std/path@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("@std/fs@1").unwrap()),
+        JsrDepPackageReq::npm(PackageReq::from_str("express@5").unwrap()),
+      ]
+    );
+    assert_eq!(missing_declared_deps(&declared, &declared), vec![]);
+  }
+
+  #[test]
+  fn test_unfurling_without_type_references() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"/// <reference types="lib/types.d.ts" />
+import foo from "lib/foo.ts";
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let mut diagnostics = Vec::new();
+    let unfurled_source = unfurler.unfurl_with_options(
+      &specifier,
+      &source,
+      &UnfurlOptions {
+        unfurl_type_references: false,
+        ..Default::default()
+      },
+      &mut |diagnostic| diagnostics.push(diagnostic),
+    );
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"/// <reference types="lib/types.d.ts" />
+import foo from "./lib/foo.ts";
+"#
+    );
+
+    // they're unfurled by default
+    let (unfurled_source, _) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"/// <reference types="./lib/types.d.ts" />
+import foo from "./lib/foo.ts";
+"#
+    );
+  }
+
+  #[test]
+  fn test_unfurling_uppercase_schemes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .allowed_schemes(Some(HashSet::from(["file".to_string()])))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import a from "HTTPS://example.com/x.js";
+import b from "https://example.com/x.js";
+import fs from "NODE:fs";
+import path from "node:path";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    // the uppercase scheme is written in its normalized form
+    assert_eq!(
+      unfurled_source,
+      r#"import a from "https://example.com/x.js";
+import b from "https://example.com/x.js";
+import fs from "NODE:fs";
+import path from "node:path";
+"#
+    );
+    let resolved = diagnostics
+      .iter()
+      .map(|d| match d {
+        SpecifierUnfurlerDiagnostic::DisallowedScheme {
+          resolved,
+          text_info,
+          range,
+          ..
+        } => (text_info.range_text(range), resolved.as_str()),
+        _ => unreachable!("unexpected diagnostic: {:?}", d),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      resolved,
+      vec![
+        ("HTTPS://example.com/x.js", "https://example.com/x.js"),
+        ("https://example.com/x.js", "https://example.com/x.js"),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_unfurling_import_meta_url_in_calls() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"const data = await Deno.readTextFile(new URL("data.json", import.meta.url));
+const bytes = Deno.readFileSync(new URL("lib/data.bin", import.meta.url), {});
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    let expected_source = r#"const data = await Deno.readTextFile(new URL("./data.json", import.meta.url));
+const bytes = Deno.readFileSync(new URL("./lib/data.bin", import.meta.url), {});
+"#;
+    assert_eq!(unfurled_source, expected_source);
+  }
+
+  #[test]
+  fn test_unfurling_directory_referrer() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let dir_url = ModuleSpecifier::from_directory_path(&cwd).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+import b from "./b.ts";
+import outside from "../outside.ts";
+"#;
+    let source = parse_ast(
+      &ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap(),
+      source_code,
+    );
+    let mut diagnostics = Vec::new();
+    let unfurled_source = unfurler.unfurl(&dir_url, &source, &mut |d| {
+      diagnostics.push(d);
+    });
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import foo from "./lib/foo.ts";
+import b from "./b.ts";
+import outside from "./../outside.ts";
+"#
+    );
+    assert_eq!(
+      relative_url(&dir_url.join("lib/foo.ts").unwrap(), &dir_url).as_deref(),
+      Some("./lib/foo.ts")
+    );
+    assert_eq!(relative_url(&dir_url, &dir_url).as_deref(), Some("./"));
+  }
+
+  #[test]
+  fn test_tally() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import fs from "fs";
+import path from "path";
+import empty from "";
+const a = await import(expr);
+"#;
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      tally(&diagnostics),
+      BTreeMap::from([
+        ("bare-node-builtin", 2),
+        ("empty-specifier", 1),
+        ("unanalyzable-dynamic-import", 1),
+      ])
+    );
+    assert_eq!(tally(&[]), BTreeMap::new());
+  }
+
+  /// A file system of in memory files, so sloppy imports can be tested
+  /// without touching the disk. Directories are implied by the file paths.
+  #[derive(Debug, Default)]
+  struct InMemoryFs {
+    files: HashMap<PathBuf, Vec<u8>>,
+    /// Symlinked paths along with their targets.
+    symlinks: Vec<(PathBuf, PathBuf)>,
+  }
+
+  impl InMemoryFs {
+    fn new(files: &[(PathBuf, &str)]) -> Self {
+      Self {
+        files: files
+          .iter()
+          .map(|(path, text)| (path.clone(), text.as_bytes().to_vec()))
+          .collect(),
+        symlinks: Vec::new(),
+      }
+    }
+
+    fn with_symlink(mut self, path: PathBuf, target: PathBuf) -> Self {
+      self.symlinks.push((path, target));
+      self
+    }
+
+    fn resolve_symlinks(&self, path: &Path) -> PathBuf {
+      for (link, target) in &self.symlinks {
+        if let Ok(rest) = path.strip_prefix(link) {
+          return self.resolve_symlinks(&target.join(rest));
+        }
+      }
+      path.to_path_buf()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+      self
+        .files
+        .keys()
+        .any(|file| file != path && file.starts_with(path))
+    }
+
+    fn stat(is_file: bool, size: u64) -> FsStat {
+      FsStat {
+        is_file,
+        is_directory: !is_file,
+        is_symlink: false,
+        size,
+        mtime: None,
+        atime: None,
+        birthtime: None,
+        dev: 0,
+        ino: 0,
+        mode: 0,
+        nlink: 0,
+        uid: 0,
+        gid: 0,
+        rdev: 0,
+        blksize: 0,
+        blocks: 0,
+        is_block_device: false,
+        is_char_device: false,
+        is_fifo: false,
+        is_socket: false,
+      }
+    }
+
+    fn not_found() -> FsError {
+      FsError::Io(std::io::ErrorKind::NotFound.into())
+    }
+  }
+
+  #[async_trait::async_trait(?Send)]
+  impl FileSystem for InMemoryFs {
+    fn cwd(&self) -> FsResult<PathBuf> {
+      Err(FsError::NotSupported)
+    }
+    fn tmp_dir(&self) -> FsResult<PathBuf> {
+      Err(FsError::NotSupported)
+    }
+    fn chdir(&self, _path: &Path) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    fn umask(&self, _mask: Option<u32>) -> FsResult<u32> {
+      Err(FsError::NotSupported)
+    }
+
+    fn open_sync(
+      &self,
+      _path: &Path,
+      _options: OpenOptions,
+    ) -> FsResult<Rc<dyn File>> {
+      Err(FsError::NotSupported)
+    }
+    async fn open_async(
+      &self,
+      _path: PathBuf,
+      _options: OpenOptions,
+    ) -> FsResult<Rc<dyn File>> {
+      Err(FsError::NotSupported)
+    }
+
+    fn mkdir_sync(
+      &self,
+      _path: &Path,
+      _recursive: bool,
+      _mode: u32,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn mkdir_async(
+      &self,
+      _path: PathBuf,
+      _recursive: bool,
+      _mode: u32,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn chmod_sync(&self, _path: &Path, _mode: u32) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn chmod_async(&self, _path: PathBuf, _mode: u32) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn chown_sync(
+      &self,
+      _path: &Path,
+      _uid: Option<u32>,
+      _gid: Option<u32>,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn chown_async(
+      &self,
+      _path: PathBuf,
+      _uid: Option<u32>,
+      _gid: Option<u32>,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn remove_sync(&self, _path: &Path, _recursive: bool) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn remove_async(
+      &self,
+      _path: PathBuf,
+      _recursive: bool,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn copy_file_sync(&self, _oldpath: &Path, _newpath: &Path) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn copy_file_async(
+      &self,
+      _oldpath: PathBuf,
+      _newpath: PathBuf,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn cp_sync(&self, _path: &Path, _new_path: &Path) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn cp_async(
+      &self,
+      _path: PathBuf,
+      _new_path: PathBuf,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
 
-          if !success {
-            let start_pos = parsed_source
-              .text_info()
-              .line_start(dep.argument_range.start.line)
-              + dep.argument_range.start.character;
-            let end_pos = parsed_source
-              .text_info()
-              .line_start(dep.argument_range.end.line)
-              + dep.argument_range.end.character;
-            diagnostic_reporter(
-              SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport {
-                specifier: url.to_owned(),
-                range: SourceRange::new(start_pos, end_pos),
-                text_info: parsed_source.text_info().clone(),
-              },
-            );
-          }
+    fn stat_sync(&self, path: &Path) -> FsResult<FsStat> {
+      let path = &self.resolve_symlinks(path);
+      if let Some(data) = self.files.get(path) {
+        Ok(Self::stat(true, data.len() as u64))
+      } else if self.is_dir(path) {
+        Ok(Self::stat(false, 0))
+      } else {
+        Err(Self::not_found())
+      }
+    }
+    async fn stat_async(&self, path: PathBuf) -> FsResult<FsStat> {
+      self.stat_sync(&path)
+    }
+
+    fn lstat_sync(&self, path: &Path) -> FsResult<FsStat> {
+      self.stat_sync(path)
+    }
+    async fn lstat_async(&self, path: PathBuf) -> FsResult<FsStat> {
+      self.stat_sync(&path)
+    }
+
+    fn realpath_sync(&self, path: &Path) -> FsResult<PathBuf> {
+      let real_path = self.resolve_symlinks(path);
+      self.stat_sync(&real_path).map(|_| real_path)
+    }
+    async fn realpath_async(&self, path: PathBuf) -> FsResult<PathBuf> {
+      self.realpath_sync(&path)
+    }
+
+    fn read_dir_sync(&self, path: &Path) -> FsResult<Vec<FsDirEntry>> {
+      let path = &self.resolve_symlinks(path);
+      if !self.is_dir(path) {
+        return Err(Self::not_found());
+      }
+      let mut entries = Vec::<FsDirEntry>::new();
+      for file in self.files.keys() {
+        let Ok(rest) = file.strip_prefix(path) else {
+          continue;
+        };
+        let mut components = rest.components();
+        let Some(name) = components.next() else {
+          continue;
+        };
+        let name = name.as_os_str().to_string_lossy().to_string();
+        if entries.iter().any(|entry| entry.name == name) {
+          continue;
         }
+        let is_file = components.next().is_none();
+        entries.push(FsDirEntry {
+          name,
+          is_file,
+          is_directory: !is_file,
+          is_symlink: false,
+        });
       }
+      Ok(entries)
     }
-    for ts_ref in &module_info.ts_references {
-      let specifier_with_range = match ts_ref {
-        TypeScriptReference::Path(range) => range,
-        TypeScriptReference::Types(range) => range,
-      };
-      analyze_specifier(
-        &specifier_with_range.text,
-        &specifier_with_range.range,
-        &mut text_changes,
-      );
+    async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>> {
+      self.read_dir_sync(&path)
     }
-    for specifier_with_range in &module_info.jsdoc_imports {
-      analyze_specifier(
-        &specifier_with_range.text,
-        &specifier_with_range.range,
-        &mut text_changes,
-      );
+
+    fn rename_sync(&self, _oldpath: &Path, _newpath: &Path) -> FsResult<()> {
+      Err(FsError::NotSupported)
     }
-    if let Some(specifier_with_range) = &module_info.jsx_import_source {
-      analyze_specifier(
-        &specifier_with_range.text,
-        &specifier_with_range.range,
-        &mut text_changes,
-      );
+    async fn rename_async(
+      &self,
+      _oldpath: PathBuf,
+      _newpath: PathBuf,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
     }
 
-    let rewritten_text = deno_ast::apply_text_changes(
-      parsed_source.text_info().text_str(),
-      text_changes,
-    );
-    rewritten_text
-  }
-}
+    fn link_sync(&self, _oldpath: &Path, _newpath: &Path) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn link_async(
+      &self,
+      _oldpath: PathBuf,
+      _newpath: PathBuf,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
 
-fn relative_url(
-  resolved: &ModuleSpecifier,
-  referrer: &ModuleSpecifier,
-) -> String {
-  if resolved.scheme() == "file" {
-    format!("./{}", referrer.make_relative(resolved).unwrap())
-  } else {
-    resolved.to_string()
-  }
-}
+    fn symlink_sync(
+      &self,
+      _oldpath: &Path,
+      _newpath: &Path,
+      _file_type: Option<FsFileType>,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn symlink_async(
+      &self,
+      _oldpath: PathBuf,
+      _newpath: PathBuf,
+      _file_type: Option<FsFileType>,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
 
-fn to_range(
-  parsed_source: &ParsedSource,
-  range: &deno_graph::PositionRange,
-) -> std::ops::Range<usize> {
-  let mut range = range
-    .as_source_range(parsed_source.text_info())
-    .as_byte_range(parsed_source.text_info().range().start);
-  let text = &parsed_source.text_info().text_str()[range.clone()];
-  if text.starts_with('"') || text.starts_with('\'') {
-    range.start += 1;
+    fn read_link_sync(&self, _path: &Path) -> FsResult<PathBuf> {
+      Err(FsError::NotSupported)
+    }
+    async fn read_link_async(&self, _path: PathBuf) -> FsResult<PathBuf> {
+      Err(FsError::NotSupported)
+    }
+
+    fn truncate_sync(&self, _path: &Path, _len: u64) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn truncate_async(&self, _path: PathBuf, _len: u64) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn utime_sync(
+      &self,
+      _path: &Path,
+      _atime_secs: i64,
+      _atime_nanos: u32,
+      _mtime_secs: i64,
+      _mtime_nanos: u32,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+    async fn utime_async(
+      &self,
+      _path: PathBuf,
+      _atime_secs: i64,
+      _atime_nanos: u32,
+      _mtime_secs: i64,
+      _mtime_nanos: u32,
+    ) -> FsResult<()> {
+      Err(FsError::NotSupported)
+    }
+
+    fn read_file_sync(&self, path: &Path) -> FsResult<Vec<u8>> {
+      self
+        .files
+        .get(&self.resolve_symlinks(path))
+        .cloned()
+        .ok_or_else(Self::not_found)
+    }
+    async fn read_file_async(&self, path: PathBuf) -> FsResult<Vec<u8>> {
+      self.read_file_sync(&path)
+    }
   }
-  if text.ends_with('"') || text.ends_with('\'') {
-    range.end -= 1;
+
+  #[test]
+  fn test_unfurling_in_memory_fs() {
+    let root = if cfg!(windows) {
+      PathBuf::from("C:\\project")
+    } else {
+      PathBuf::from("/project")
+    };
+    let fs = Arc::new(InMemoryFs::new(&[
+      (root.join("mod.ts"), ""),
+      (root.join("b.ts"), "export default 1;"),
+      (root.join("c.ts"), "export default 2;"),
+      (root.join("dir/index.ts"), "export default 3;"),
+    ]));
+    let mapped_resolver = create_mapped_resolver(&root);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .check_file_existence(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(root.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "./b";
+import c from "./c.js";
+import dir from "./dir";
+import missing from "./missing.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"import b from "./b.ts";
+import c from "./c.ts";
+import dir from "./dir/index.ts";
+import missing from "./missing.ts";
+"#
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+      diagnostics[0],
+      SpecifierUnfurlerDiagnostic::MissingFile { .. }
+    ));
   }
-  range
-}
 
-#[cfg(test)]
-mod tests {
-  use std::sync::Arc;
+  #[test]
+  fn test_unfurling_possible_trailing_slash_mismatch() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import lib from "lib";
+import foo from "lib/foo.ts";
+import other from "./lib";
+"#;
+    let (_, diagnostics) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 1);
+    match &diagnostics[0] {
+      SpecifierUnfurlerDiagnostic::PossibleTrailingSlashMismatch {
+        key,
+        text_info,
+        range,
+        ..
+      } => {
+        assert_eq!(key, "lib/");
+        assert_eq!(text_info.range_text(range), "lib");
+      }
+      diagnostic => unreachable!("unexpected diagnostic: {:?}", diagnostic),
+    }
+  }
 
-  use crate::args::package_json::get_local_package_json_version_reqs;
-  use crate::args::PackageJsonDepsProvider;
+  #[test]
+  fn test_unfurling_relative_without_leading_dot() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import b from "b.ts";
+import index from "baz/index.js";
+const c = await import("b.ts");
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import b from "./b.ts";
+import index from "./baz/index.js";
+const c = await import("./b.ts");
+"#
+    );
 
-  use super::*;
-  use deno_ast::MediaType;
-  use deno_ast::ModuleSpecifier;
-  use deno_core::serde_json::json;
-  use deno_core::url::Url;
-  use deno_runtime::deno_fs::RealFs;
-  use deno_runtime::deno_node::PackageJson;
-  use import_map::ImportMapWithDiagnostics;
-  use indexmap::IndexMap;
-  use pretty_assertions::assert_eq;
-  use test_util::testdata_path;
+    // the same happens from a nested module
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("baz/mod.ts")).unwrap();
+    let (unfurled_source, _) =
+      unfurl_text(&unfurler, &specifier, "import index from \"index.js\";\n");
+    assert_eq!(unfurled_source, "import index from \"./index.js\";\n");
+  }
 
-  fn parse_ast(specifier: &Url, source_code: &str) -> ParsedSource {
-    let media_type = MediaType::from_specifier(specifier);
-    deno_ast::parse_module(deno_ast::ParseParams {
-      specifier: specifier.clone(),
-      media_type,
-      capture_tokens: false,
-      maybe_syntax: None,
-      scope_analysis: false,
-      text_info: deno_ast::SourceTextInfo::new(source_code.into()),
-    })
-    .unwrap()
+  #[test]
+  fn test_merge_dep_sets() {
+    let npm =
+      |req: &str| JsrDepPackageReq::npm(PackageReq::from_str(req).unwrap());
+    let jsr =
+      |req: &str| JsrDepPackageReq::jsr(PackageReq::from_str(req).unwrap());
+    let a = HashSet::from([npm("chalk@4"), npm("express@5"), jsr("@std/fs@1")]);
+    let b = HashSet::from([
+      npm("chalk@5"),
+      npm("express@^5.0.1"),
+      jsr("@std/fs@1"),
+      npm("@std/fs@2"),
+    ]);
+    let (merged, conflicts) = merge_dep_sets(&[a, b]);
+    assert_eq!(
+      merged,
+      HashSet::from([
+        npm("chalk@4"),
+        npm("chalk@5"),
+        npm("express@5"),
+        npm("express@^5.0.1"),
+        jsr("@std/fs@1"),
+        npm("@std/fs@2"),
+      ])
+    );
+    // express's requirements intersect and the jsr and npm `@std/fs` are
+    // different packages
+    assert_eq!(
+      conflicts,
+      vec![DepConflict {
+        name: "chalk".to_string(),
+        reqs: vec![npm("chalk@4"), npm("chalk@5")],
+      }]
+    );
+    assert_eq!(merge_dep_sets(&[]), (HashSet::new(), vec![]));
   }
 
   #[test]
-  fn test_unfurling() {
+  fn test_unfurl_collect() {
     let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import express from "express";
+import foo from "lib/foo.ts";
+import fs from "fs";
+const a = await import(expr);
+"#;
+    let source = parse_ast(&specifier, source_code);
+    let (unfurled_source, diagnostics) =
+      unfurler.unfurl_collect(&specifier, &source);
+    assert_eq!(
+      unfurled_source,
+      r#"import express from "npm:express@5";
+import foo from "./lib/foo.ts";
+import fs from "fs";
+const a = await import(expr);
+"#
+    );
+    assert_eq!(
+      diagnostics.iter().map(|d| d.code()).collect::<Vec<_>>(),
+      vec!["bare-node-builtin", "unanalyzable-dynamic-import"]
+    );
+    // it's the same as collecting with the reporter
+    let (reported_source, reported_diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, reported_source);
+    assert_eq!(diagnostics.len(), reported_diagnostics.len());
+  }
 
+  #[test]
+  fn test_unfurling_null_import_map_entry() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
     let deno_json_url =
       ModuleSpecifier::from_file_path(cwd.join("deno.json")).unwrap();
     let value = json!({
       "imports": {
-        "express": "npm:express@5",
+        "blocked": null,
+        "vendor/": null,
         "lib/": "./lib/",
-        "fizz": "./fizz/mod.ts",
-        "@std/fs": "npm:@jsr/std__fs@1",
       }
     });
     let ImportMapWithDiagnostics { import_map, .. } =
       import_map::parse_from_value(deno_json_url, value).unwrap();
-    let mut package_json = PackageJson::empty(cwd.join("package.json"));
-    package_json.dependencies =
-      Some(IndexMap::from([("chalk".to_string(), "5".to_string())]));
     let mapped_resolver = MappedSpecifierResolver::new(
       Some(Arc::new(import_map)),
-      Arc::new(PackageJsonDepsProvider::new(Some(
-        get_local_package_json_version_reqs(&package_json),
-      ))),
+      Arc::new(PackageJsonDepsProvider::new(None)),
     );
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import blocked from "blocked";
+import vendored from "vendor/mod.ts";
+import foo from "lib/foo.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    // rather than being resolved relative to the module
+    assert_eq!(
+      unfurled_source,
+      r#"import blocked from "blocked";
+import vendored from "vendor/mod.ts";
+import foo from "./lib/foo.ts";
+"#
+    );
+    let keys = diagnostics
+      .iter()
+      .map(|d| match d {
+        SpecifierUnfurlerDiagnostic::NullImportMapEntry { key, .. } => {
+          key.as_str()
+        }
+        _ => unreachable!("unexpected diagnostic: {:?}", d),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(keys, vec!["blocked", "vendor/"]);
+  }
 
-    let fs = Arc::new(RealFs);
-    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
-
-    let unfurler = SpecifierUnfurler::new(
-      &mapped_resolver,
-      Some(&sloppy_imports_resolver),
-      true,
+  #[test]
+  fn test_unfurling_std_aliases() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .std_aliases(true)
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import { walk } from "@std/fs/mod.ts";
+import { join } from "@std/path@1";
+import fs from "@std/fs";
+import "lib/foo.ts";
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    // import map entries still take precedence
+    assert_eq!(
+      unfurled_source,
+      r#"import { walk } from "jsr:@std/fs/mod.ts";
+import { join } from "jsr:@std/path@1";
+import fs from "npm:@jsr/std__fs@1";
+import "./lib/foo.ts";
+"#
     );
+  }
 
-    // Unfurling TS file should apply changes.
-    {
-      let source_code = r#"import express from "express";"
-import foo from "lib/foo.ts";
-import bar from "lib/bar.ts";
-import fizz from "fizz";
-import chalk from "chalk";
-import baz from "./baz";
-import b from "./b.js";
-import b2 from "./b";
-import url from "url";
-// TODO: unfurl these to jsr
-// import "npm:@jsr/std__fs@1/file";
-// import "npm:@jsr/std__fs@1";
-// import "npm:@jsr/std__fs";
-// import "@std/fs";
+  #[test]
+  fn test_unfurling_normalize_quotes() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .normalize_quotes(Some('"'))
+      .build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from 'lib/foo.ts';
+import b from './b.ts';
+import express from "express";
+const a = await import('lib/foo.ts');
+const c = await import(`lib/${name}`);
+"#;
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    // only the rewritten string specifiers get new quotes
+    assert_eq!(
+      unfurled_source,
+      r#"import foo from "./lib/foo.ts";
+import b from './b.ts';
+import express from "npm:express@5";
+const a = await import("./lib/foo.ts");
+const c = await import(`./lib/${name}`);
+"#
+    );
+  }
 
-const test1 = await import("lib/foo.ts");
-const test2 = await import(`lib/foo.ts`);
-const test3 = await import(`lib/${expr}`);
-const test4 = await import(`./lib/${expr}`);
-const test5 = await import("./lib/something.ts");
-const test6 = await import(`./lib/something.ts`);
-// will warn
-const warn1 = await import(`lib${expr}`);
-const warn2 = await import(`${expr}`);
+  #[test]
+  fn test_unfurling_canonicalize_paths() {
+    let root = if cfg!(windows) {
+      PathBuf::from("C:\\")
+    } else {
+      PathBuf::from("/")
+    };
+    let real_dir = root.join("real/pkg");
+    let link_dir = root.join("project");
+    let fs = Arc::new(
+      InMemoryFs::new(&[
+        (real_dir.join("mod.ts"), ""),
+        (real_dir.join("b.ts"), "export default 1;"),
+        (real_dir.join("lib/foo.ts"), "export default 2;"),
+      ])
+      .with_symlink(link_dir.clone(), real_dir.clone()),
+    );
+    // the import map is found at its real path, while the module is
+    // reached through the symlink
+    let mapped_resolver = create_mapped_resolver(&real_dir);
+    let sloppy_imports_resolver = SloppyImportsResolver::new(fs);
+    let specifier =
+      ModuleSpecifier::from_file_path(link_dir.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "lib/foo.ts";
+import b from "./b.ts";
 "#;
-      let specifier =
-        ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
-      let source = parse_ast(&specifier, source_code);
-      let mut d = Vec::new();
-      let mut reporter = |diagnostic| d.push(diagnostic);
-      let unfurled_source = unfurler.unfurl(&specifier, &source, &mut reporter);
-      assert_eq!(d.len(), 2);
-      assert!(
-        matches!(
-          d[0],
-          SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
-        ),
-        "{:?}",
-        d[0]
-      );
-      assert!(
-        matches!(
-          d[1],
-          SpecifierUnfurlerDiagnostic::UnanalyzableDynamicImport { .. }
-        ),
-        "{:?}",
-        d[1]
-      );
-      let expected_source = r#"import express from "npm:express@5";"
-import foo from "./lib/foo.ts";
-import bar from "./lib/bar.ts";
-import fizz from "./fizz/mod.ts";
-import chalk from "npm:chalk@5";
-import baz from "./baz/index.js";
+
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .build();
+    let (unfurled_source, _) = unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(
+      unfurled_source,
+      r#"import foo from "./../real/pkg/lib/foo.ts";
 import b from "./b.ts";
-import b2 from "./b.ts";
-import url from "node:url";
-// TODO: unfurl these to jsr
-// import "npm:@jsr/std__fs@1/file";
-// import "npm:@jsr/std__fs@1";
-// import "npm:@jsr/std__fs";
-// import "@std/fs";
+"#
+    );
 
-const test1 = await import("./lib/foo.ts");
-const test2 = await import(`./lib/foo.ts`);
-const test3 = await import(`./lib/${expr}`);
-const test4 = await import(`./lib/${expr}`);
-const test5 = await import("./lib/something.ts");
-const test6 = await import(`./lib/something.ts`);
-// will warn
-const warn1 = await import(`lib${expr}`);
-const warn2 = await import(`${expr}`);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver)
+      .sloppy_imports_resolver(Some(&sloppy_imports_resolver))
+      .canonicalize_paths(true)
+      .build();
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(
+      unfurled_source,
+      r#"import foo from "./lib/foo.ts";
+import b from "./b.ts";
+"#
+    );
+  }
+
+  #[test]
+  fn test_unfurling_test_file_import() {
+    let cwd = testdata_path().join("unfurl").to_path_buf();
+    let mapped_resolver = create_mapped_resolver(&cwd);
+    let unfurler = SpecifierUnfurlerBuilder::new(&mapped_resolver).build();
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod.ts")).unwrap();
+    let source_code = r#"import foo from "./foo_test.ts";
+import bar from "./lib/bar.test.js";
+import test from "./test.ts";
+import latest from "./latest.ts";
+import testing from "./testing/mod.ts";
 "#;
-      assert_eq!(unfurled_source, expected_source);
-    }
+    let (unfurled_source, diagnostics) =
+      unfurl_text(&unfurler, &specifier, source_code);
+    assert_eq!(unfurled_source, source_code);
+    let resolved = diagnostics
+      .iter()
+      .map(|d| match d {
+        SpecifierUnfurlerDiagnostic::TestFileImport {
+          resolved,
+          text_info,
+          range,
+          ..
+        } => {
+          assert_eq!(
+            resolved,
+            &specifier.join(text_info.range_text(range)).unwrap()
+          );
+          text_info.range_text(range)
+        }
+        _ => unreachable!("unexpected diagnostic: {:?}", d),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      resolved,
+      vec!["./foo_test.ts", "./lib/bar.test.js", "./test.ts"]
+    );
+
+    // test modules can import each other
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("mod_test.ts")).unwrap();
+    let (_, diagnostics) = unfurl_text(
+      &unfurler,
+      &specifier,
+      "import foo from \"./foo_test.ts\";\n",
+    );
+    assert_eq!(diagnostics.len(), 0);
   }
 }
@@ -0,0 +1,1508 @@
+// Generated module for benchmarking how long `deno publish` takes to
+// unfurl a module with many specifiers.
+import { a as a0 } from "lib/a.ts";
+import { a as a1 } from "lib/a.ts";
+import { a as a2 } from "lib/a.ts";
+import { a as a3 } from "lib/a.ts";
+import { a as a4 } from "lib/a.ts";
+import { a as a5 } from "lib/a.ts";
+import { a as a6 } from "lib/a.ts";
+import { a as a7 } from "lib/a.ts";
+import { a as a8 } from "lib/a.ts";
+import { a as a9 } from "lib/a.ts";
+import { a as a10 } from "lib/a.ts";
+import { a as a11 } from "lib/a.ts";
+import { a as a12 } from "lib/a.ts";
+import { a as a13 } from "lib/a.ts";
+import { a as a14 } from "lib/a.ts";
+import { a as a15 } from "lib/a.ts";
+import { a as a16 } from "lib/a.ts";
+import { a as a17 } from "lib/a.ts";
+import { a as a18 } from "lib/a.ts";
+import { a as a19 } from "lib/a.ts";
+import { a as a20 } from "lib/a.ts";
+import { a as a21 } from "lib/a.ts";
+import { a as a22 } from "lib/a.ts";
+import { a as a23 } from "lib/a.ts";
+import { a as a24 } from "lib/a.ts";
+import { a as a25 } from "lib/a.ts";
+import { a as a26 } from "lib/a.ts";
+import { a as a27 } from "lib/a.ts";
+import { a as a28 } from "lib/a.ts";
+import { a as a29 } from "lib/a.ts";
+import { a as a30 } from "lib/a.ts";
+import { a as a31 } from "lib/a.ts";
+import { a as a32 } from "lib/a.ts";
+import { a as a33 } from "lib/a.ts";
+import { a as a34 } from "lib/a.ts";
+import { a as a35 } from "lib/a.ts";
+import { a as a36 } from "lib/a.ts";
+import { a as a37 } from "lib/a.ts";
+import { a as a38 } from "lib/a.ts";
+import { a as a39 } from "lib/a.ts";
+import { a as a40 } from "lib/a.ts";
+import { a as a41 } from "lib/a.ts";
+import { a as a42 } from "lib/a.ts";
+import { a as a43 } from "lib/a.ts";
+import { a as a44 } from "lib/a.ts";
+import { a as a45 } from "lib/a.ts";
+import { a as a46 } from "lib/a.ts";
+import { a as a47 } from "lib/a.ts";
+import { a as a48 } from "lib/a.ts";
+import { a as a49 } from "lib/a.ts";
+import { a as a50 } from "lib/a.ts";
+import { a as a51 } from "lib/a.ts";
+import { a as a52 } from "lib/a.ts";
+import { a as a53 } from "lib/a.ts";
+import { a as a54 } from "lib/a.ts";
+import { a as a55 } from "lib/a.ts";
+import { a as a56 } from "lib/a.ts";
+import { a as a57 } from "lib/a.ts";
+import { a as a58 } from "lib/a.ts";
+import { a as a59 } from "lib/a.ts";
+import { a as a60 } from "lib/a.ts";
+import { a as a61 } from "lib/a.ts";
+import { a as a62 } from "lib/a.ts";
+import { a as a63 } from "lib/a.ts";
+import { a as a64 } from "lib/a.ts";
+import { a as a65 } from "lib/a.ts";
+import { a as a66 } from "lib/a.ts";
+import { a as a67 } from "lib/a.ts";
+import { a as a68 } from "lib/a.ts";
+import { a as a69 } from "lib/a.ts";
+import { a as a70 } from "lib/a.ts";
+import { a as a71 } from "lib/a.ts";
+import { a as a72 } from "lib/a.ts";
+import { a as a73 } from "lib/a.ts";
+import { a as a74 } from "lib/a.ts";
+import { a as a75 } from "lib/a.ts";
+import { a as a76 } from "lib/a.ts";
+import { a as a77 } from "lib/a.ts";
+import { a as a78 } from "lib/a.ts";
+import { a as a79 } from "lib/a.ts";
+import { a as a80 } from "lib/a.ts";
+import { a as a81 } from "lib/a.ts";
+import { a as a82 } from "lib/a.ts";
+import { a as a83 } from "lib/a.ts";
+import { a as a84 } from "lib/a.ts";
+import { a as a85 } from "lib/a.ts";
+import { a as a86 } from "lib/a.ts";
+import { a as a87 } from "lib/a.ts";
+import { a as a88 } from "lib/a.ts";
+import { a as a89 } from "lib/a.ts";
+import { a as a90 } from "lib/a.ts";
+import { a as a91 } from "lib/a.ts";
+import { a as a92 } from "lib/a.ts";
+import { a as a93 } from "lib/a.ts";
+import { a as a94 } from "lib/a.ts";
+import { a as a95 } from "lib/a.ts";
+import { a as a96 } from "lib/a.ts";
+import { a as a97 } from "lib/a.ts";
+import { a as a98 } from "lib/a.ts";
+import { a as a99 } from "lib/a.ts";
+import { a as a100 } from "lib/a.ts";
+import { a as a101 } from "lib/a.ts";
+import { a as a102 } from "lib/a.ts";
+import { a as a103 } from "lib/a.ts";
+import { a as a104 } from "lib/a.ts";
+import { a as a105 } from "lib/a.ts";
+import { a as a106 } from "lib/a.ts";
+import { a as a107 } from "lib/a.ts";
+import { a as a108 } from "lib/a.ts";
+import { a as a109 } from "lib/a.ts";
+import { a as a110 } from "lib/a.ts";
+import { a as a111 } from "lib/a.ts";
+import { a as a112 } from "lib/a.ts";
+import { a as a113 } from "lib/a.ts";
+import { a as a114 } from "lib/a.ts";
+import { a as a115 } from "lib/a.ts";
+import { a as a116 } from "lib/a.ts";
+import { a as a117 } from "lib/a.ts";
+import { a as a118 } from "lib/a.ts";
+import { a as a119 } from "lib/a.ts";
+import { a as a120 } from "lib/a.ts";
+import { a as a121 } from "lib/a.ts";
+import { a as a122 } from "lib/a.ts";
+import { a as a123 } from "lib/a.ts";
+import { a as a124 } from "lib/a.ts";
+import { a as a125 } from "lib/a.ts";
+import { a as a126 } from "lib/a.ts";
+import { a as a127 } from "lib/a.ts";
+import { a as a128 } from "lib/a.ts";
+import { a as a129 } from "lib/a.ts";
+import { a as a130 } from "lib/a.ts";
+import { a as a131 } from "lib/a.ts";
+import { a as a132 } from "lib/a.ts";
+import { a as a133 } from "lib/a.ts";
+import { a as a134 } from "lib/a.ts";
+import { a as a135 } from "lib/a.ts";
+import { a as a136 } from "lib/a.ts";
+import { a as a137 } from "lib/a.ts";
+import { a as a138 } from "lib/a.ts";
+import { a as a139 } from "lib/a.ts";
+import { a as a140 } from "lib/a.ts";
+import { a as a141 } from "lib/a.ts";
+import { a as a142 } from "lib/a.ts";
+import { a as a143 } from "lib/a.ts";
+import { a as a144 } from "lib/a.ts";
+import { a as a145 } from "lib/a.ts";
+import { a as a146 } from "lib/a.ts";
+import { a as a147 } from "lib/a.ts";
+import { a as a148 } from "lib/a.ts";
+import { a as a149 } from "lib/a.ts";
+import { a as a150 } from "lib/a.ts";
+import { a as a151 } from "lib/a.ts";
+import { a as a152 } from "lib/a.ts";
+import { a as a153 } from "lib/a.ts";
+import { a as a154 } from "lib/a.ts";
+import { a as a155 } from "lib/a.ts";
+import { a as a156 } from "lib/a.ts";
+import { a as a157 } from "lib/a.ts";
+import { a as a158 } from "lib/a.ts";
+import { a as a159 } from "lib/a.ts";
+import { a as a160 } from "lib/a.ts";
+import { a as a161 } from "lib/a.ts";
+import { a as a162 } from "lib/a.ts";
+import { a as a163 } from "lib/a.ts";
+import { a as a164 } from "lib/a.ts";
+import { a as a165 } from "lib/a.ts";
+import { a as a166 } from "lib/a.ts";
+import { a as a167 } from "lib/a.ts";
+import { a as a168 } from "lib/a.ts";
+import { a as a169 } from "lib/a.ts";
+import { a as a170 } from "lib/a.ts";
+import { a as a171 } from "lib/a.ts";
+import { a as a172 } from "lib/a.ts";
+import { a as a173 } from "lib/a.ts";
+import { a as a174 } from "lib/a.ts";
+import { a as a175 } from "lib/a.ts";
+import { a as a176 } from "lib/a.ts";
+import { a as a177 } from "lib/a.ts";
+import { a as a178 } from "lib/a.ts";
+import { a as a179 } from "lib/a.ts";
+import { a as a180 } from "lib/a.ts";
+import { a as a181 } from "lib/a.ts";
+import { a as a182 } from "lib/a.ts";
+import { a as a183 } from "lib/a.ts";
+import { a as a184 } from "lib/a.ts";
+import { a as a185 } from "lib/a.ts";
+import { a as a186 } from "lib/a.ts";
+import { a as a187 } from "lib/a.ts";
+import { a as a188 } from "lib/a.ts";
+import { a as a189 } from "lib/a.ts";
+import { a as a190 } from "lib/a.ts";
+import { a as a191 } from "lib/a.ts";
+import { a as a192 } from "lib/a.ts";
+import { a as a193 } from "lib/a.ts";
+import { a as a194 } from "lib/a.ts";
+import { a as a195 } from "lib/a.ts";
+import { a as a196 } from "lib/a.ts";
+import { a as a197 } from "lib/a.ts";
+import { a as a198 } from "lib/a.ts";
+import { a as a199 } from "lib/a.ts";
+import { a as a200 } from "lib/a.ts";
+import { a as a201 } from "lib/a.ts";
+import { a as a202 } from "lib/a.ts";
+import { a as a203 } from "lib/a.ts";
+import { a as a204 } from "lib/a.ts";
+import { a as a205 } from "lib/a.ts";
+import { a as a206 } from "lib/a.ts";
+import { a as a207 } from "lib/a.ts";
+import { a as a208 } from "lib/a.ts";
+import { a as a209 } from "lib/a.ts";
+import { a as a210 } from "lib/a.ts";
+import { a as a211 } from "lib/a.ts";
+import { a as a212 } from "lib/a.ts";
+import { a as a213 } from "lib/a.ts";
+import { a as a214 } from "lib/a.ts";
+import { a as a215 } from "lib/a.ts";
+import { a as a216 } from "lib/a.ts";
+import { a as a217 } from "lib/a.ts";
+import { a as a218 } from "lib/a.ts";
+import { a as a219 } from "lib/a.ts";
+import { a as a220 } from "lib/a.ts";
+import { a as a221 } from "lib/a.ts";
+import { a as a222 } from "lib/a.ts";
+import { a as a223 } from "lib/a.ts";
+import { a as a224 } from "lib/a.ts";
+import { a as a225 } from "lib/a.ts";
+import { a as a226 } from "lib/a.ts";
+import { a as a227 } from "lib/a.ts";
+import { a as a228 } from "lib/a.ts";
+import { a as a229 } from "lib/a.ts";
+import { a as a230 } from "lib/a.ts";
+import { a as a231 } from "lib/a.ts";
+import { a as a232 } from "lib/a.ts";
+import { a as a233 } from "lib/a.ts";
+import { a as a234 } from "lib/a.ts";
+import { a as a235 } from "lib/a.ts";
+import { a as a236 } from "lib/a.ts";
+import { a as a237 } from "lib/a.ts";
+import { a as a238 } from "lib/a.ts";
+import { a as a239 } from "lib/a.ts";
+import { a as a240 } from "lib/a.ts";
+import { a as a241 } from "lib/a.ts";
+import { a as a242 } from "lib/a.ts";
+import { a as a243 } from "lib/a.ts";
+import { a as a244 } from "lib/a.ts";
+import { a as a245 } from "lib/a.ts";
+import { a as a246 } from "lib/a.ts";
+import { a as a247 } from "lib/a.ts";
+import { a as a248 } from "lib/a.ts";
+import { a as a249 } from "lib/a.ts";
+import { a as a250 } from "lib/a.ts";
+import { a as a251 } from "lib/a.ts";
+import { a as a252 } from "lib/a.ts";
+import { a as a253 } from "lib/a.ts";
+import { a as a254 } from "lib/a.ts";
+import { a as a255 } from "lib/a.ts";
+import { a as a256 } from "lib/a.ts";
+import { a as a257 } from "lib/a.ts";
+import { a as a258 } from "lib/a.ts";
+import { a as a259 } from "lib/a.ts";
+import { a as a260 } from "lib/a.ts";
+import { a as a261 } from "lib/a.ts";
+import { a as a262 } from "lib/a.ts";
+import { a as a263 } from "lib/a.ts";
+import { a as a264 } from "lib/a.ts";
+import { a as a265 } from "lib/a.ts";
+import { a as a266 } from "lib/a.ts";
+import { a as a267 } from "lib/a.ts";
+import { a as a268 } from "lib/a.ts";
+import { a as a269 } from "lib/a.ts";
+import { a as a270 } from "lib/a.ts";
+import { a as a271 } from "lib/a.ts";
+import { a as a272 } from "lib/a.ts";
+import { a as a273 } from "lib/a.ts";
+import { a as a274 } from "lib/a.ts";
+import { a as a275 } from "lib/a.ts";
+import { a as a276 } from "lib/a.ts";
+import { a as a277 } from "lib/a.ts";
+import { a as a278 } from "lib/a.ts";
+import { a as a279 } from "lib/a.ts";
+import { a as a280 } from "lib/a.ts";
+import { a as a281 } from "lib/a.ts";
+import { a as a282 } from "lib/a.ts";
+import { a as a283 } from "lib/a.ts";
+import { a as a284 } from "lib/a.ts";
+import { a as a285 } from "lib/a.ts";
+import { a as a286 } from "lib/a.ts";
+import { a as a287 } from "lib/a.ts";
+import { a as a288 } from "lib/a.ts";
+import { a as a289 } from "lib/a.ts";
+import { a as a290 } from "lib/a.ts";
+import { a as a291 } from "lib/a.ts";
+import { a as a292 } from "lib/a.ts";
+import { a as a293 } from "lib/a.ts";
+import { a as a294 } from "lib/a.ts";
+import { a as a295 } from "lib/a.ts";
+import { a as a296 } from "lib/a.ts";
+import { a as a297 } from "lib/a.ts";
+import { a as a298 } from "lib/a.ts";
+import { a as a299 } from "lib/a.ts";
+import { a as a300 } from "lib/a.ts";
+import { a as a301 } from "lib/a.ts";
+import { a as a302 } from "lib/a.ts";
+import { a as a303 } from "lib/a.ts";
+import { a as a304 } from "lib/a.ts";
+import { a as a305 } from "lib/a.ts";
+import { a as a306 } from "lib/a.ts";
+import { a as a307 } from "lib/a.ts";
+import { a as a308 } from "lib/a.ts";
+import { a as a309 } from "lib/a.ts";
+import { a as a310 } from "lib/a.ts";
+import { a as a311 } from "lib/a.ts";
+import { a as a312 } from "lib/a.ts";
+import { a as a313 } from "lib/a.ts";
+import { a as a314 } from "lib/a.ts";
+import { a as a315 } from "lib/a.ts";
+import { a as a316 } from "lib/a.ts";
+import { a as a317 } from "lib/a.ts";
+import { a as a318 } from "lib/a.ts";
+import { a as a319 } from "lib/a.ts";
+import { a as a320 } from "lib/a.ts";
+import { a as a321 } from "lib/a.ts";
+import { a as a322 } from "lib/a.ts";
+import { a as a323 } from "lib/a.ts";
+import { a as a324 } from "lib/a.ts";
+import { a as a325 } from "lib/a.ts";
+import { a as a326 } from "lib/a.ts";
+import { a as a327 } from "lib/a.ts";
+import { a as a328 } from "lib/a.ts";
+import { a as a329 } from "lib/a.ts";
+import { a as a330 } from "lib/a.ts";
+import { a as a331 } from "lib/a.ts";
+import { a as a332 } from "lib/a.ts";
+import { a as a333 } from "lib/a.ts";
+import { a as a334 } from "lib/a.ts";
+import { a as a335 } from "lib/a.ts";
+import { a as a336 } from "lib/a.ts";
+import { a as a337 } from "lib/a.ts";
+import { a as a338 } from "lib/a.ts";
+import { a as a339 } from "lib/a.ts";
+import { a as a340 } from "lib/a.ts";
+import { a as a341 } from "lib/a.ts";
+import { a as a342 } from "lib/a.ts";
+import { a as a343 } from "lib/a.ts";
+import { a as a344 } from "lib/a.ts";
+import { a as a345 } from "lib/a.ts";
+import { a as a346 } from "lib/a.ts";
+import { a as a347 } from "lib/a.ts";
+import { a as a348 } from "lib/a.ts";
+import { a as a349 } from "lib/a.ts";
+import { a as a350 } from "lib/a.ts";
+import { a as a351 } from "lib/a.ts";
+import { a as a352 } from "lib/a.ts";
+import { a as a353 } from "lib/a.ts";
+import { a as a354 } from "lib/a.ts";
+import { a as a355 } from "lib/a.ts";
+import { a as a356 } from "lib/a.ts";
+import { a as a357 } from "lib/a.ts";
+import { a as a358 } from "lib/a.ts";
+import { a as a359 } from "lib/a.ts";
+import { a as a360 } from "lib/a.ts";
+import { a as a361 } from "lib/a.ts";
+import { a as a362 } from "lib/a.ts";
+import { a as a363 } from "lib/a.ts";
+import { a as a364 } from "lib/a.ts";
+import { a as a365 } from "lib/a.ts";
+import { a as a366 } from "lib/a.ts";
+import { a as a367 } from "lib/a.ts";
+import { a as a368 } from "lib/a.ts";
+import { a as a369 } from "lib/a.ts";
+import { a as a370 } from "lib/a.ts";
+import { a as a371 } from "lib/a.ts";
+import { a as a372 } from "lib/a.ts";
+import { a as a373 } from "lib/a.ts";
+import { a as a374 } from "lib/a.ts";
+import { a as a375 } from "lib/a.ts";
+import { a as a376 } from "lib/a.ts";
+import { a as a377 } from "lib/a.ts";
+import { a as a378 } from "lib/a.ts";
+import { a as a379 } from "lib/a.ts";
+import { a as a380 } from "lib/a.ts";
+import { a as a381 } from "lib/a.ts";
+import { a as a382 } from "lib/a.ts";
+import { a as a383 } from "lib/a.ts";
+import { a as a384 } from "lib/a.ts";
+import { a as a385 } from "lib/a.ts";
+import { a as a386 } from "lib/a.ts";
+import { a as a387 } from "lib/a.ts";
+import { a as a388 } from "lib/a.ts";
+import { a as a389 } from "lib/a.ts";
+import { a as a390 } from "lib/a.ts";
+import { a as a391 } from "lib/a.ts";
+import { a as a392 } from "lib/a.ts";
+import { a as a393 } from "lib/a.ts";
+import { a as a394 } from "lib/a.ts";
+import { a as a395 } from "lib/a.ts";
+import { a as a396 } from "lib/a.ts";
+import { a as a397 } from "lib/a.ts";
+import { a as a398 } from "lib/a.ts";
+import { a as a399 } from "lib/a.ts";
+import { a as a400 } from "lib/a.ts";
+import { a as a401 } from "lib/a.ts";
+import { a as a402 } from "lib/a.ts";
+import { a as a403 } from "lib/a.ts";
+import { a as a404 } from "lib/a.ts";
+import { a as a405 } from "lib/a.ts";
+import { a as a406 } from "lib/a.ts";
+import { a as a407 } from "lib/a.ts";
+import { a as a408 } from "lib/a.ts";
+import { a as a409 } from "lib/a.ts";
+import { a as a410 } from "lib/a.ts";
+import { a as a411 } from "lib/a.ts";
+import { a as a412 } from "lib/a.ts";
+import { a as a413 } from "lib/a.ts";
+import { a as a414 } from "lib/a.ts";
+import { a as a415 } from "lib/a.ts";
+import { a as a416 } from "lib/a.ts";
+import { a as a417 } from "lib/a.ts";
+import { a as a418 } from "lib/a.ts";
+import { a as a419 } from "lib/a.ts";
+import { a as a420 } from "lib/a.ts";
+import { a as a421 } from "lib/a.ts";
+import { a as a422 } from "lib/a.ts";
+import { a as a423 } from "lib/a.ts";
+import { a as a424 } from "lib/a.ts";
+import { a as a425 } from "lib/a.ts";
+import { a as a426 } from "lib/a.ts";
+import { a as a427 } from "lib/a.ts";
+import { a as a428 } from "lib/a.ts";
+import { a as a429 } from "lib/a.ts";
+import { a as a430 } from "lib/a.ts";
+import { a as a431 } from "lib/a.ts";
+import { a as a432 } from "lib/a.ts";
+import { a as a433 } from "lib/a.ts";
+import { a as a434 } from "lib/a.ts";
+import { a as a435 } from "lib/a.ts";
+import { a as a436 } from "lib/a.ts";
+import { a as a437 } from "lib/a.ts";
+import { a as a438 } from "lib/a.ts";
+import { a as a439 } from "lib/a.ts";
+import { a as a440 } from "lib/a.ts";
+import { a as a441 } from "lib/a.ts";
+import { a as a442 } from "lib/a.ts";
+import { a as a443 } from "lib/a.ts";
+import { a as a444 } from "lib/a.ts";
+import { a as a445 } from "lib/a.ts";
+import { a as a446 } from "lib/a.ts";
+import { a as a447 } from "lib/a.ts";
+import { a as a448 } from "lib/a.ts";
+import { a as a449 } from "lib/a.ts";
+import { a as a450 } from "lib/a.ts";
+import { a as a451 } from "lib/a.ts";
+import { a as a452 } from "lib/a.ts";
+import { a as a453 } from "lib/a.ts";
+import { a as a454 } from "lib/a.ts";
+import { a as a455 } from "lib/a.ts";
+import { a as a456 } from "lib/a.ts";
+import { a as a457 } from "lib/a.ts";
+import { a as a458 } from "lib/a.ts";
+import { a as a459 } from "lib/a.ts";
+import { a as a460 } from "lib/a.ts";
+import { a as a461 } from "lib/a.ts";
+import { a as a462 } from "lib/a.ts";
+import { a as a463 } from "lib/a.ts";
+import { a as a464 } from "lib/a.ts";
+import { a as a465 } from "lib/a.ts";
+import { a as a466 } from "lib/a.ts";
+import { a as a467 } from "lib/a.ts";
+import { a as a468 } from "lib/a.ts";
+import { a as a469 } from "lib/a.ts";
+import { a as a470 } from "lib/a.ts";
+import { a as a471 } from "lib/a.ts";
+import { a as a472 } from "lib/a.ts";
+import { a as a473 } from "lib/a.ts";
+import { a as a474 } from "lib/a.ts";
+import { a as a475 } from "lib/a.ts";
+import { a as a476 } from "lib/a.ts";
+import { a as a477 } from "lib/a.ts";
+import { a as a478 } from "lib/a.ts";
+import { a as a479 } from "lib/a.ts";
+import { a as a480 } from "lib/a.ts";
+import { a as a481 } from "lib/a.ts";
+import { a as a482 } from "lib/a.ts";
+import { a as a483 } from "lib/a.ts";
+import { a as a484 } from "lib/a.ts";
+import { a as a485 } from "lib/a.ts";
+import { a as a486 } from "lib/a.ts";
+import { a as a487 } from "lib/a.ts";
+import { a as a488 } from "lib/a.ts";
+import { a as a489 } from "lib/a.ts";
+import { a as a490 } from "lib/a.ts";
+import { a as a491 } from "lib/a.ts";
+import { a as a492 } from "lib/a.ts";
+import { a as a493 } from "lib/a.ts";
+import { a as a494 } from "lib/a.ts";
+import { a as a495 } from "lib/a.ts";
+import { a as a496 } from "lib/a.ts";
+import { a as a497 } from "lib/a.ts";
+import { a as a498 } from "lib/a.ts";
+import { a as a499 } from "lib/a.ts";
+
+const loaders = [
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+  () => import("lib/b.ts"),
+];
+
+export const count: number = loaders.length + [
+  a0,
+  a1,
+  a2,
+  a3,
+  a4,
+  a5,
+  a6,
+  a7,
+  a8,
+  a9,
+  a10,
+  a11,
+  a12,
+  a13,
+  a14,
+  a15,
+  a16,
+  a17,
+  a18,
+  a19,
+  a20,
+  a21,
+  a22,
+  a23,
+  a24,
+  a25,
+  a26,
+  a27,
+  a28,
+  a29,
+  a30,
+  a31,
+  a32,
+  a33,
+  a34,
+  a35,
+  a36,
+  a37,
+  a38,
+  a39,
+  a40,
+  a41,
+  a42,
+  a43,
+  a44,
+  a45,
+  a46,
+  a47,
+  a48,
+  a49,
+  a50,
+  a51,
+  a52,
+  a53,
+  a54,
+  a55,
+  a56,
+  a57,
+  a58,
+  a59,
+  a60,
+  a61,
+  a62,
+  a63,
+  a64,
+  a65,
+  a66,
+  a67,
+  a68,
+  a69,
+  a70,
+  a71,
+  a72,
+  a73,
+  a74,
+  a75,
+  a76,
+  a77,
+  a78,
+  a79,
+  a80,
+  a81,
+  a82,
+  a83,
+  a84,
+  a85,
+  a86,
+  a87,
+  a88,
+  a89,
+  a90,
+  a91,
+  a92,
+  a93,
+  a94,
+  a95,
+  a96,
+  a97,
+  a98,
+  a99,
+  a100,
+  a101,
+  a102,
+  a103,
+  a104,
+  a105,
+  a106,
+  a107,
+  a108,
+  a109,
+  a110,
+  a111,
+  a112,
+  a113,
+  a114,
+  a115,
+  a116,
+  a117,
+  a118,
+  a119,
+  a120,
+  a121,
+  a122,
+  a123,
+  a124,
+  a125,
+  a126,
+  a127,
+  a128,
+  a129,
+  a130,
+  a131,
+  a132,
+  a133,
+  a134,
+  a135,
+  a136,
+  a137,
+  a138,
+  a139,
+  a140,
+  a141,
+  a142,
+  a143,
+  a144,
+  a145,
+  a146,
+  a147,
+  a148,
+  a149,
+  a150,
+  a151,
+  a152,
+  a153,
+  a154,
+  a155,
+  a156,
+  a157,
+  a158,
+  a159,
+  a160,
+  a161,
+  a162,
+  a163,
+  a164,
+  a165,
+  a166,
+  a167,
+  a168,
+  a169,
+  a170,
+  a171,
+  a172,
+  a173,
+  a174,
+  a175,
+  a176,
+  a177,
+  a178,
+  a179,
+  a180,
+  a181,
+  a182,
+  a183,
+  a184,
+  a185,
+  a186,
+  a187,
+  a188,
+  a189,
+  a190,
+  a191,
+  a192,
+  a193,
+  a194,
+  a195,
+  a196,
+  a197,
+  a198,
+  a199,
+  a200,
+  a201,
+  a202,
+  a203,
+  a204,
+  a205,
+  a206,
+  a207,
+  a208,
+  a209,
+  a210,
+  a211,
+  a212,
+  a213,
+  a214,
+  a215,
+  a216,
+  a217,
+  a218,
+  a219,
+  a220,
+  a221,
+  a222,
+  a223,
+  a224,
+  a225,
+  a226,
+  a227,
+  a228,
+  a229,
+  a230,
+  a231,
+  a232,
+  a233,
+  a234,
+  a235,
+  a236,
+  a237,
+  a238,
+  a239,
+  a240,
+  a241,
+  a242,
+  a243,
+  a244,
+  a245,
+  a246,
+  a247,
+  a248,
+  a249,
+  a250,
+  a251,
+  a252,
+  a253,
+  a254,
+  a255,
+  a256,
+  a257,
+  a258,
+  a259,
+  a260,
+  a261,
+  a262,
+  a263,
+  a264,
+  a265,
+  a266,
+  a267,
+  a268,
+  a269,
+  a270,
+  a271,
+  a272,
+  a273,
+  a274,
+  a275,
+  a276,
+  a277,
+  a278,
+  a279,
+  a280,
+  a281,
+  a282,
+  a283,
+  a284,
+  a285,
+  a286,
+  a287,
+  a288,
+  a289,
+  a290,
+  a291,
+  a292,
+  a293,
+  a294,
+  a295,
+  a296,
+  a297,
+  a298,
+  a299,
+  a300,
+  a301,
+  a302,
+  a303,
+  a304,
+  a305,
+  a306,
+  a307,
+  a308,
+  a309,
+  a310,
+  a311,
+  a312,
+  a313,
+  a314,
+  a315,
+  a316,
+  a317,
+  a318,
+  a319,
+  a320,
+  a321,
+  a322,
+  a323,
+  a324,
+  a325,
+  a326,
+  a327,
+  a328,
+  a329,
+  a330,
+  a331,
+  a332,
+  a333,
+  a334,
+  a335,
+  a336,
+  a337,
+  a338,
+  a339,
+  a340,
+  a341,
+  a342,
+  a343,
+  a344,
+  a345,
+  a346,
+  a347,
+  a348,
+  a349,
+  a350,
+  a351,
+  a352,
+  a353,
+  a354,
+  a355,
+  a356,
+  a357,
+  a358,
+  a359,
+  a360,
+  a361,
+  a362,
+  a363,
+  a364,
+  a365,
+  a366,
+  a367,
+  a368,
+  a369,
+  a370,
+  a371,
+  a372,
+  a373,
+  a374,
+  a375,
+  a376,
+  a377,
+  a378,
+  a379,
+  a380,
+  a381,
+  a382,
+  a383,
+  a384,
+  a385,
+  a386,
+  a387,
+  a388,
+  a389,
+  a390,
+  a391,
+  a392,
+  a393,
+  a394,
+  a395,
+  a396,
+  a397,
+  a398,
+  a399,
+  a400,
+  a401,
+  a402,
+  a403,
+  a404,
+  a405,
+  a406,
+  a407,
+  a408,
+  a409,
+  a410,
+  a411,
+  a412,
+  a413,
+  a414,
+  a415,
+  a416,
+  a417,
+  a418,
+  a419,
+  a420,
+  a421,
+  a422,
+  a423,
+  a424,
+  a425,
+  a426,
+  a427,
+  a428,
+  a429,
+  a430,
+  a431,
+  a432,
+  a433,
+  a434,
+  a435,
+  a436,
+  a437,
+  a438,
+  a439,
+  a440,
+  a441,
+  a442,
+  a443,
+  a444,
+  a445,
+  a446,
+  a447,
+  a448,
+  a449,
+  a450,
+  a451,
+  a452,
+  a453,
+  a454,
+  a455,
+  a456,
+  a457,
+  a458,
+  a459,
+  a460,
+  a461,
+  a462,
+  a463,
+  a464,
+  a465,
+  a466,
+  a467,
+  a468,
+  a469,
+  a470,
+  a471,
+  a472,
+  a473,
+  a474,
+  a475,
+  a476,
+  a477,
+  a478,
+  a479,
+  a480,
+  a481,
+  a482,
+  a483,
+  a484,
+  a485,
+  a486,
+  a487,
+  a488,
+  a489,
+  a490,
+  a491,
+  a492,
+  a493,
+  a494,
+  a495,
+  a496,
+  a497,
+  a498,
+  a499,
+].length;